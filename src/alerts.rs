@@ -0,0 +1,101 @@
+use anyhow::{bail, Result};
+use serde::Deserialize;
+
+use crate::procstat::CpuBreakdown;
+
+/// A threshold on a named metric, configured as `[[alerts]]` in the config file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertRule {
+    /// Metric name as understood by `App::metric`, e.g. `cpu` or `psi.memory.some.avg10`
+    pub metric: String,
+    pub above: f64,
+    /// Shown instead of the metric name when the alert fires
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActiveAlert {
+    pub name: String,
+    pub value: f64,
+    pub threshold: f64,
+}
+
+/// Returns the rules whose metric currently exceeds its threshold. Metrics that
+/// are unavailable on this host never fire.
+pub fn evaluate<F>(rules: &[AlertRule], metric: F) -> Vec<ActiveAlert>
+where
+    F: Fn(&str) -> Option<f64>,
+{
+    rules
+        .iter()
+        .filter_map(|rule| {
+            let value = metric(&rule.metric)?;
+            (value > rule.above).then(|| ActiveAlert {
+                name: rule.name.clone().unwrap_or_else(|| rule.metric.clone()),
+                value,
+                threshold: rule.above,
+            })
+        })
+        .collect()
+}
+
+/// Rejects rules naming a metric `App::metric` can never produce, so a typo
+/// does not leave an alert that silently never fires.
+pub fn validate(rules: &[AlertRule]) -> Result<()> {
+    for rule in rules {
+        if !is_known_metric(&rule.metric) {
+            bail!("unknown metric `{}`", rule.metric);
+        }
+    }
+    Ok(())
+}
+
+fn is_known_metric(name: &str) -> bool {
+    let parts: Vec<&str> = name.split('.').collect();
+    match parts.as_slice() {
+        ["cpu"] | ["memory"] | ["swap"] | ["temperature"] => true,
+        ["process", name @ .., "cpu"] => !name.is_empty() && name.iter().all(|part| !part.is_empty()),
+        ["cpu", state] => *state == "idle" || CpuBreakdown::default().busy().iter().any(|(name, _)| name == state),
+        ["psi", resource, kind, window] => {
+            matches!(*resource, "cpu" | "memory" | "io")
+                && matches!(*kind, "some" | "full")
+                && matches!(*window, "avg10" | "avg60" | "avg300")
+        }
+        ["processes", state] => matches!(*state, "zombie" | "unreaped" | "disk_sleep" | "stopped"),
+        ["sensor", chip, label @ ..] | ["plugin", chip, label @ ..] => !chip.is_empty() && !label.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(metric: &str) -> AlertRule {
+        AlertRule { metric: metric.to_string(), above: 0.0, name: None }
+    }
+
+    #[test]
+    fn known_metrics_pass_validation() {
+        let metrics = [
+            "cpu",
+            "cpu.iowait",
+            "process.top.cpu",
+            "process.python3.11.cpu",
+            "psi.io.full.avg60",
+            "processes.zombie",
+            "plugin.gpu.temp.max",
+        ];
+        let rules: Vec<_> = metrics.into_iter().map(rule).collect();
+        assert!(validate(&rules).is_ok());
+    }
+
+    #[test]
+    fn unknown_metrics_are_reported() {
+        for metric in ["cpus", "cpu.busy", "process.cpu", "psi.disk.some.avg10", "processes.running", "plugin.gpu"] {
+            let err = validate(&[rule(metric)]).unwrap_err();
+            assert_eq!(err.to_string(), format!("unknown metric `{}`", metric));
+        }
+    }
+}
@@ -1,16 +1,72 @@
 use anyhow::Result;
-use sysinfo::{System, Pid, Networks, Disks};
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use sysinfo::{System, Networks, Disks};
+
+use crate::alerts::{self, ActiveAlert, AlertRule};
+use crate::bandwidth::{Bandwidth, Estimator};
+use crate::cgroup::{self, CgroupLimits, Perspective};
+use crate::config::Config;
+use crate::connections::{self, Connection, ConnectionFilter};
+use crate::dashboard::Dashboard;
+use crate::fds::{self, OpenFile};
+use crate::interfaces::{self, Interface};
+use crate::logs::{LogMonitor, LogView, LogsConfig};
+use crate::meminfo::{self, MemoryBreakdown};
+use crate::plugins::{Plugin, PluginState};
+use crate::power::{self, PowerStatus};
+use crate::pressure::{self, PressureStats};
+use crate::process_control::{self, IoClass, IoPriority, ProcessAction};
+use crate::process_events::{self, ProcessEvent};
+use crate::process_status::{ProcessStatus, StateCounts, ZombieTracker};
+use crate::procstat::{self, CpuBreakdown, CpuStats, StatSample};
+use crate::remote::{ClientMessage, RemoteHost, Snapshot};
+use crate::sensors::{self, Sensor};
+use crate::services::{self, ServiceInfo};
+use crate::system_info::get_load_average;
+use crate::watch::WatchList;
+
+pub const OVERVIEW_TAB: usize = 0;
+pub const PROCESSES_TAB: usize = 1;
+pub const NETWORK_TAB: usize = 2;
+pub const DISKS_TAB: usize = 3;
+pub const SERVICES_TAB: usize = 4;
+pub const HOSTS_TAB: usize = 5;
+pub const MEMORY_TAB: usize = 6;
+pub const CPU_TAB: usize = 7;
+pub const CUSTOM_TAB: usize = 8;
+pub const LOGS_TAB: usize = 9;
+pub const SENSORS_TAB: usize = 10;
+const TAB_COUNT: usize = 11;
+/// Nice, I/O priority, affinity and open fds cost several syscalls per process, so
+/// they are re-read this often; in between only new and selected processes are read.
+const DETAILS_REFRESH: Duration = Duration::from_secs(10);
 
 pub type AppResult<T> = Result<T, Box<dyn std::error::Error>>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProcessInfo {
     pub pid: u32,
+    pub parent: Option<u32>,
     pub name: String,
     pub cpu_usage: f32,
     pub memory: u64,
-    pub status: String,
+    pub status: ProcessStatus,
     pub start_time: u64,
+    #[serde(default)]
+    pub nice: Option<i32>,
+    #[serde(default)]
+    pub io_priority: Option<IoPriority>,
+    #[serde(default)]
+    pub affinity: Option<Vec<usize>>,
+    #[serde(default)]
+    pub fds: Option<usize>,
+    /// Network rate attributed to the process, when one could be estimated
+    #[serde(default)]
+    pub net: Option<Bandwidth>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -19,6 +75,52 @@ pub enum SortBy {
     Name,
     Cpu,
     Memory,
+    Fds,
+    Net,
+}
+
+/// How per-process CPU usage is expressed, named after the equivalent modes in top.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CpuMode {
+    /// Percentage of one core; a busy multi-threaded process can exceed 100%
+    Irix,
+    /// Percentage of the whole machine, on the same 0-100 scale as the CPU gauge
+    Solaris,
+}
+
+impl CpuMode {
+    /// Converts sysinfo's per-core percentage into this mode.
+    pub fn apply(self, cpu_usage: f32, cpu_count: usize) -> f32 {
+        match self {
+            CpuMode::Irix => cpu_usage,
+            CpuMode::Solaris => cpu_usage / cpu_count.max(1) as f32,
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            CpuMode::Irix => "Irix (% of one core)",
+            CpuMode::Solaris => "Solaris (% of all cores)",
+        }
+    }
+}
+
+/// A modal dialog on the Processes tab. Editing dialogs lead to `Confirm`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Dialog {
+    Renice { pid: u32, name: String, nice: i32 },
+    Ionice { pid: u32, name: String, priority: IoPriority },
+    Affinity { pid: u32, name: String, cores: Vec<bool>, cursor: usize },
+    Confirm { action: ProcessAction, name: String },
+    OpenFiles { pid: u32, name: String, files: Vec<OpenFile>, scroll: usize },
+}
+
+/// Scroll position of a table. The renderer writes it back each frame so the
+/// viewport only moves when the selection would otherwise leave it.
+#[derive(Debug, Default)]
+pub struct TableView {
+    pub offset: Cell<usize>,
+    pub height: Cell<usize>, // rows visible in the last frame
 }
 
 pub struct App {
@@ -26,14 +128,60 @@ pub struct App {
     pub networks: Networks,
     pub disks: Disks,
     pub processes: Vec<ProcessInfo>,
-    pub selected_process: usize,
+    pub selected_process: usize, // index into visible_processes()
+    pub problem_filter: bool, // only zombie, D-state and stopped processes
+    pub zombies: ZombieTracker,
+    details_read: Option<Instant>,
+    pub process_view: TableView,
+    pub process_events: VecDeque<ProcessEvent>, // oldest first
+    pub bandwidth: Estimator,
+    pub watch: WatchList,
+    pub config_path: Option<PathBuf>, // where the watch list is saved
+    pub services: Vec<ServiceInfo>,
+    pub selected_service: usize,
+    pub service_view: TableView,
     pub current_tab: usize,
     pub sort_by: SortBy,
     pub sort_ascending: bool,
+    pub cpu_mode: CpuMode,
     pub cpu_history: Vec<f32>,
     pub memory_history: Vec<f32>,
+    pub swap_history: Vec<f32>,
+    pub memory_breakdown: Option<MemoryBreakdown>,
+    pub pressure: Option<PressureStats>,
+    pub pressure_history: Vec<(f32, f32, f32)>, // some avg10 for (cpu, memory, io)
+    pub power: Option<PowerStatus>, // None without a battery
+    pub battery_history: Vec<f32>,
+    pub sensors: Vec<Sensor>,
+    pub selected_sensor: usize,
+    pub sensor_view: TableView,
+    pub cpu_stats: Option<CpuStats>,
+    pub cpu_breakdown_history: Vec<CpuBreakdown>,
+    cpu_stat_sample: Option<StatSample>,
     pub network_history: Vec<(u64, u64)>, // (received, transmitted)
+    pub connections: Vec<Connection>, // local only, refreshed while the Network tab is open
+    pub connection_filter: ConnectionFilter,
+    pub selected_connection: usize,
+    pub connection_view: TableView,
+    pub interfaces: Vec<Interface>,
+    pub show_virtual_interfaces: bool, // loopback, veth, bridges, ...
     pub disk_usage: Vec<(String, u64, u64)>, // (name, used, total)
+    pub perspective: Perspective,
+    pub cgroup: Option<CgroupLimits>,
+    cgroup_cpu_sample: Option<(u64, Instant)>, // (usage_usec, taken at)
+    pub hosts: Vec<RemoteHost>,
+    pub selected_host: usize,
+    pub host_view: TableView,
+    pub dashboards: Vec<Dashboard>, // shown as tabs after the built-in ones
+    pub plugins: Vec<Plugin>,
+    pub custom_metrics: Vec<PluginState>, // latest plugin output, local or from the agent
+    pub logs: LogMonitor,
+    pub log_view: LogView,
+    remote_info: Option<(SystemInfo, usize)>, // (system info, interface count)
+    pub alert_rules: Vec<AlertRule>,
+    pub alerts: Vec<ActiveAlert>,
+    pub dialog: Option<Dialog>,
+    pub status_message: Option<String>,
 }
 
 impl App {
@@ -50,24 +198,83 @@ impl App {
             disks,
             processes: Vec::new(),
             selected_process: 0,
+            problem_filter: false,
+            zombies: ZombieTracker::default(),
+            details_read: None,
+            process_view: TableView::default(),
+            process_events: VecDeque::new(),
+            bandwidth: Estimator::default(),
+            watch: WatchList::default(),
+            config_path: None,
+            services: Vec::new(),
+            selected_service: 0,
+            service_view: TableView::default(),
             current_tab: 0,
             sort_by: SortBy::Cpu,
             sort_ascending: false,
+            cpu_mode: CpuMode::Irix,
             cpu_history: Vec::new(),
             memory_history: Vec::new(),
+            swap_history: Vec::new(),
+            memory_breakdown: None,
+            pressure: None,
+            pressure_history: Vec::new(),
+            power: None,
+            battery_history: Vec::new(),
+            sensors: Vec::new(),
+            selected_sensor: 0,
+            sensor_view: TableView::default(),
+            cpu_stats: None,
+            cpu_breakdown_history: Vec::new(),
+            cpu_stat_sample: None,
             network_history: Vec::new(),
+            connections: Vec::new(),
+            connection_filter: ConnectionFilter::default(),
+            selected_connection: 0,
+            connection_view: TableView::default(),
+            interfaces: Vec::new(),
+            show_virtual_interfaces: true,
             disk_usage: Vec::new(),
+            perspective: Perspective::Auto,
+            cgroup: cgroup::detect(),
+            cgroup_cpu_sample: None,
+            hosts: Vec::new(),
+            selected_host: 0,
+            host_view: TableView::default(),
+            dashboards: Vec::new(),
+            plugins: Vec::new(),
+            custom_metrics: Vec::new(),
+            logs: LogMonitor::new(&LogsConfig::default()).expect("built-in log patterns are valid"),
+            log_view: LogView::default(),
+            remote_info: None,
+            alert_rules: Vec::new(),
+            alerts: Vec::new(),
+            dialog: None,
+            status_message: None,
         }
     }
 
     pub async fn update(&mut self) {
+        // Log files are read on this machine, even while showing a remote host
+        self.logs.update();
+
+        if self.is_remote() {
+            self.apply_remote_snapshot();
+            self.update_alerts();
+            return;
+        }
+
         self.system.refresh_all();
         self.networks.refresh();
         self.disks.refresh();
+        self.cgroup = cgroup::detect();
         
         // Update processes
         self.update_processes();
         
+        // Update services
+        self.update_services();
+        
         // Update system metrics
         self.update_system_metrics();
         
@@ -76,28 +283,202 @@ impl App {
         
         // Update disk usage
         self.update_disk_usage();
+
+        // Update pressure stall information
+        self.update_pressure();
+
+        // Update battery state
+        self.update_power();
+
+        // Update temperatures, fans and voltages
+        self.sensors = sensors::read(Path::new(sensors::DEFAULT_DIR), &self.sensors);
+        self.clamp_sensor_selection();
+
+        self.custom_metrics = self.plugins.iter().map(|plugin| plugin.lock().unwrap().clone()).collect();
+
+        // Resolving socket owners walks every process's fds, so only do it while shown
+        if self.current_tab == NETWORK_TAB {
+            self.connections = connections::collect(&self.processes);
+            self.clamp_connection_selection();
+        }
+
+        self.update_alerts();
+    }
+
+    pub fn is_remote(&self) -> bool {
+        !self.hosts.is_empty()
+    }
+
+    /// Replaces the displayed data with the latest snapshot from the selected host.
+    fn apply_remote_snapshot(&mut self) {
+        let Some(host) = self.hosts.get(self.selected_host) else {
+            return;
+        };
+        let host = host.lock().unwrap();
+        let Some(snapshot) = host.snapshot.clone() else {
+            return;
+        };
+        let address = host.address.clone();
+        drop(host);
+
+        let Snapshot {
+            mut system_info,
+            processes,
+            services,
+            cpu_history,
+            memory_history,
+            swap_history,
+            memory_breakdown,
+            pressure,
+            pressure_history,
+            power,
+            battery_history,
+            sensors,
+            cpu_stats,
+            cpu_breakdown_history,
+            network_history,
+            disk_usage,
+            interface_count,
+            interfaces,
+            custom_metrics,
+            ..
+        } = snapshot;
+        let previous = std::mem::replace(&mut self.processes, processes);
+        self.record_process_events(&previous);
+        self.zombies.update(&self.processes);
+        self.watch.update(&self.processes, chrono::Local::now());
+        self.services = services;
+        self.cpu_history = cpu_history;
+        self.memory_history = memory_history;
+        self.swap_history = swap_history;
+        self.memory_breakdown = memory_breakdown;
+        self.pressure = pressure;
+        self.pressure_history = pressure_history;
+        self.power = power;
+        self.battery_history = battery_history;
+        self.sensors = sensors;
+        self.clamp_sensor_selection();
+        self.cpu_stats = cpu_stats;
+        self.cpu_breakdown_history = cpu_breakdown_history;
+        self.network_history = network_history;
+        self.disk_usage = disk_usage;
+        self.interfaces = interfaces;
+        self.custom_metrics = custom_metrics;
+        system_info.source = address;
+        self.remote_info = Some((system_info, interface_count));
+
+        self.sort_processes();
+        self.clamp_process_selection();
+        if self.selected_service >= self.services.len() {
+            self.selected_service = self.services.len().saturating_sub(1);
+        }
+    }
+
+    /// Number of CPUs on the displayed host.
+    pub fn cpu_count(&self) -> usize {
+        match &self.remote_info {
+            Some((info, _)) => info.cpu_count,
+            None => self.system.cpus().len(),
+        }
+    }
+
+    /// A process's CPU usage in the active `CpuMode`.
+    pub fn process_cpu(&self, cpu_usage: f32) -> f32 {
+        self.cpu_mode.apply(cpu_usage, self.cpu_count())
+    }
+
+    pub fn toggle_cpu_mode(&mut self) {
+        self.cpu_mode = match self.cpu_mode {
+            CpuMode::Irix => CpuMode::Solaris,
+            CpuMode::Solaris => CpuMode::Irix,
+        };
+        self.sort_processes();
+        self.update_alerts();
+    }
+
+    pub fn interface_count(&self) -> usize {
+        match &self.remote_info {
+            Some((_, count)) => *count,
+            None => self.networks.len(),
+        }
     }
 
     fn update_processes(&mut self) {
-        self.processes.clear();
-        
+        let selected = self.selected_process_info().map(|p| p.pid);
+        let previous = std::mem::take(&mut self.processes);
+        let refresh_details = self.details_read.is_none_or(|read| read.elapsed() >= DETAILS_REFRESH);
+        if refresh_details {
+            self.details_read = Some(Instant::now());
+        }
+        let known: HashMap<u32, &ProcessInfo> = previous.iter().map(|p| (p.pid, p)).collect();
+
         for (pid, process) in self.system.processes() {
+            let pid = pid.as_u32();
+            // The selected process is always fresh, so the info bar shows the effect of actions
+            let cached = known
+                .get(&pid)
+                .filter(|p| !refresh_details && Some(pid) != selected && p.start_time == process.start_time());
+            let (nice, io_priority, affinity, fds) = match cached {
+                Some(p) => (p.nice, p.io_priority, p.affinity.clone(), p.fds),
+                None => (
+                    process_control::get_nice(pid),
+                    process_control::get_io_priority(pid),
+                    process_control::get_affinity(pid),
+                    fds::count(pid),
+                ),
+            };
             self.processes.push(ProcessInfo {
-                pid: pid.as_u32(),
+                pid,
+                parent: process.parent().map(|ppid| ppid.as_u32()),
                 name: process.name().to_string(),
                 cpu_usage: process.cpu_usage(),
                 memory: process.memory(),
-                status: format!("{:?}", process.status()),
+                status: process.status().into(),
                 start_time: process.start_time(),
+                nice,
+                io_priority,
+                affinity,
+                fds,
+                net: None,
             });
         }
+        let pids: Vec<u32> = self.processes.iter().map(|p| p.pid).collect();
+        let mut rates = self.bandwidth.estimate(&pids);
+        for process in &mut self.processes {
+            process.net = rates.remove(&process.pid);
+        }
+        self.record_process_events(&previous);
+        self.zombies.update(&self.processes);
+        self.watch.update(&self.processes, chrono::Local::now());
         
         // Sort processes
         self.sort_processes();
         
         // Ensure selected process is within bounds
-        if self.selected_process >= self.processes.len() {
-            self.selected_process = self.processes.len().saturating_sub(1);
+        self.clamp_process_selection();
+    }
+
+    /// Logs the processes that started or exited since `previous` was taken.
+    fn record_process_events(&mut self, previous: &[ProcessInfo]) {
+        // Everything would look new on the first refresh
+        if previous.is_empty() {
+            return;
+        }
+        let events = process_events::diff(previous, &self.processes, chrono::Local::now());
+        self.process_events.extend(events);
+        while self.process_events.len() > process_events::MAX_EVENTS {
+            self.process_events.pop_front();
+        }
+    }
+
+    fn update_services(&mut self) {
+        self.services = match services::system_slice() {
+            Some(slice) => services::collect(&slice, &self.processes),
+            None => Vec::new(),
+        };
+
+        if self.selected_service >= self.services.len() {
+            self.selected_service = self.services.len().saturating_sub(1);
         }
     }
 
@@ -118,10 +499,12 @@ impl App {
                 }
             }
             SortBy::Cpu => {
+                let (mode, cores) = (self.cpu_mode, self.cpu_count());
+                let cpu = |p: &ProcessInfo| mode.apply(p.cpu_usage, cores);
                 if self.sort_ascending {
-                    self.processes.sort_by(|a, b| a.cpu_usage.partial_cmp(&b.cpu_usage).unwrap_or(std::cmp::Ordering::Equal));
+                    self.processes.sort_by(|a, b| cpu(a).partial_cmp(&cpu(b)).unwrap_or(std::cmp::Ordering::Equal));
                 } else {
-                    self.processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap_or(std::cmp::Ordering::Equal));
+                    self.processes.sort_by(|a, b| cpu(b).partial_cmp(&cpu(a)).unwrap_or(std::cmp::Ordering::Equal));
                 }
             }
             SortBy::Memory => {
@@ -131,23 +514,209 @@ impl App {
                     self.processes.sort_by(|a, b| b.memory.cmp(&a.memory));
                 }
             }
+            SortBy::Fds => {
+                if self.sort_ascending {
+                    self.processes.sort_by_key(|p| p.fds);
+                } else {
+                    self.processes.sort_by_key(|p| std::cmp::Reverse(p.fds));
+                }
+            }
+            SortBy::Net => {
+                let total = |p: &ProcessInfo| p.net.map(|net| net.total());
+                if self.sort_ascending {
+                    self.processes.sort_by_key(total);
+                } else {
+                    self.processes.sort_by_key(|p| std::cmp::Reverse(total(p)));
+                }
+            }
         }
+
+        // Watched processes stay on top; the sort is stable, so they keep their order
+        let watch = &self.watch;
+        self.processes.sort_by_key(|p| !watch.is_watched(&p.name));
     }
 
     fn update_system_metrics(&mut self) {
         // CPU usage
-        let cpu_usage = self.system.global_cpu_info().cpu_usage();
+        let cpu_usage = self
+            .container_cpu_usage()
+            .unwrap_or_else(|| self.system.global_cpu_info().cpu_usage());
         self.cpu_history.push(cpu_usage);
         if self.cpu_history.len() > 60 {
             self.cpu_history.remove(0);
         }
 
         // Memory usage
-        let memory_usage = (self.system.used_memory() as f32 / self.system.total_memory() as f32) * 100.0;
+        let (used_memory, total_memory) = self.memory_totals();
+        let memory_usage = (used_memory as f32 / total_memory as f32) * 100.0;
         self.memory_history.push(memory_usage);
         if self.memory_history.len() > 60 {
             self.memory_history.remove(0);
         }
+
+        // Swap usage
+        let swap_usage = if self.system.total_swap() > 0 {
+            (self.system.used_swap() as f32 / self.system.total_swap() as f32) * 100.0
+        } else {
+            0.0
+        };
+        self.swap_history.push(swap_usage);
+        if self.swap_history.len() > 60 {
+            self.swap_history.remove(0);
+        }
+
+        self.memory_breakdown = meminfo::read();
+
+        // CPU time breakdown
+        if let Some(sample) = procstat::read() {
+            if let Some(previous) = &self.cpu_stat_sample {
+                let stats = sample.stats_since(previous);
+                self.cpu_breakdown_history.push(stats.global);
+                if self.cpu_breakdown_history.len() > 60 {
+                    self.cpu_breakdown_history.remove(0);
+                }
+                self.cpu_stats = Some(stats);
+            }
+            self.cpu_stat_sample = Some(sample);
+        }
+    }
+
+    /// Returns the active cgroup limits when the container perspective applies.
+    pub fn container_limits(&self) -> Option<&CgroupLimits> {
+        match self.perspective {
+            Perspective::Host => None,
+            Perspective::Auto => self.cgroup.as_ref().filter(|c| c.is_limited()),
+            Perspective::Container => self.cgroup.as_ref(),
+        }
+    }
+
+    fn memory_totals(&self) -> (u64, u64) {
+        let host_total = self.system.total_memory();
+        match self.container_limits() {
+            Some(limits) => (
+                limits.memory_current.unwrap_or_else(|| self.system.used_memory()),
+                limits.memory_max.map_or(host_total, |max| max.min(host_total)),
+            ),
+            None => (self.system.used_memory(), host_total),
+        }
+    }
+
+    // CPU time used by the cgroup since the last tick, as a share of its quota.
+    fn container_cpu_usage(&mut self) -> Option<f32> {
+        let limits = self.container_limits()?;
+        let usage = limits.cpu_usage_usec?;
+        let cores = limits.cpu_quota.unwrap_or(self.system.cpus().len() as f64);
+        let now = Instant::now();
+        let previous = self.cgroup_cpu_sample.replace((usage, now));
+
+        let (prev_usage, prev_time) = previous?;
+        let elapsed_usec = now.duration_since(prev_time).as_micros() as f64;
+        if elapsed_usec <= 0.0 || cores <= 0.0 {
+            return None;
+        }
+        let percent = usage.saturating_sub(prev_usage) as f64 / (elapsed_usec * cores) * 100.0;
+        Some(percent.clamp(0.0, 100.0) as f32)
+    }
+
+    fn update_pressure(&mut self) {
+        // Inside a container the cgroup's own pressure files are the relevant ones
+        let cgroup_pressure = self.container_limits().and_then(|limits| pressure::read_cgroup(&limits.path));
+        self.pressure = cgroup_pressure.or_else(pressure::read_system);
+
+        if let Some(stats) = &self.pressure {
+            let some10 = |p: &Option<pressure::Pressure>| p.map_or(0.0, |p| p.some.avg10);
+            self.pressure_history.push((some10(&stats.cpu), some10(&stats.memory), some10(&stats.io)));
+            if self.pressure_history.len() > 60 {
+                self.pressure_history.remove(0);
+            }
+        }
+    }
+
+    fn update_power(&mut self) {
+        self.power = power::read(Path::new(power::DEFAULT_DIR));
+        match &self.power {
+            Some(status) => {
+                self.battery_history.push(status.percent());
+                if self.battery_history.len() > 60 {
+                    self.battery_history.remove(0);
+                }
+            }
+            None => self.battery_history.clear(),
+        }
+    }
+
+    fn update_alerts(&mut self) {
+        self.alerts = alerts::evaluate(&self.alert_rules, |name| self.metric(name));
+    }
+
+    /// Looks up a metric by the name used in alert rules.
+    pub fn metric(&self, name: &str) -> Option<f64> {
+        let parts: Vec<&str> = name.split('.').collect();
+        match parts.as_slice() {
+            ["cpu"] => self.cpu_history.last().map(|&v| v as f64),
+            ["memory"] => self.memory_history.last().map(|&v| v as f64),
+            ["swap"] => self.swap_history.last().map(|&v| v as f64),
+            // Per-process metrics follow the active CpuMode
+            ["process", "top", "cpu"] => self
+                .processes
+                .iter()
+                .map(|p| self.process_cpu(p.cpu_usage) as f64)
+                .reduce(f64::max),
+            // Process names such as `python3.11` contain dots too
+            ["process", name @ .., "cpu"] if !name.is_empty() => {
+                let name = name.join(".");
+                self.processes
+                    .iter()
+                    .filter(|p| p.name == name)
+                    .map(|p| self.process_cpu(p.cpu_usage) as f64)
+                    .reduce(|a, b| a + b)
+            }
+            ["cpu", state] => {
+                let breakdown = self.cpu_stats.as_ref()?.global;
+                let value = match *state {
+                    "idle" => breakdown.idle,
+                    _ => breakdown.busy().into_iter().find(|(name, _)| name == state)?.1,
+                };
+                Some(value as f64)
+            }
+            ["psi", resource, kind, window] => {
+                let pressure = self.pressure.as_ref()?.get(resource)?;
+                let averages = match *kind {
+                    "some" => pressure.some,
+                    "full" => pressure.full?,
+                    _ => return None,
+                };
+                match *window {
+                    "avg10" => Some(averages.avg10 as f64),
+                    "avg60" => Some(averages.avg60 as f64),
+                    "avg300" => Some(averages.avg300 as f64),
+                    _ => None,
+                }
+            }
+            ["processes", state] => {
+                let counts = self.state_counts();
+                let count = match *state {
+                    "zombie" => counts.zombie,
+                    "unreaped" => counts.unreaped,
+                    "disk_sleep" => counts.disk_sleep,
+                    "stopped" => counts.stopped,
+                    _ => return None,
+                };
+                Some(count as f64)
+            }
+            ["temperature"] => sensors::max_temperature(&self.sensors),
+            ["sensor", chip, label @ ..] if !label.is_empty() => {
+                let label = label.join(".");
+                self.sensors.iter().find(|s| s.chip == *chip && s.label == label).map(|s| s.value)
+            }
+            // Plugin metric names may themselves contain dots
+            ["plugin", plugin, metric @ ..] if !metric.is_empty() => {
+                let metric = metric.join(".");
+                let plugin = self.custom_metrics.iter().find(|p| p.name == *plugin)?;
+                plugin.metrics.iter().find(|m| m.name == metric).map(|m| m.value)
+            }
+            _ => None,
+        }
     }
 
     fn update_network_stats(&mut self) {
@@ -163,6 +732,8 @@ impl App {
         if self.network_history.len() > 60 {
             self.network_history.remove(0);
         }
+
+        self.interfaces = interfaces::collect(&self.networks);
     }
 
     fn update_disk_usage(&mut self) {
@@ -179,38 +750,461 @@ impl App {
     }
 
     pub fn next_tab(&mut self) {
-        self.current_tab = (self.current_tab + 1) % 4; // 4 tabs: Overview, Processes, Network, Disks
+        self.current_tab = (self.current_tab + 1) % self.tab_count();
     }
 
     pub fn previous_tab(&mut self) {
         if self.current_tab > 0 {
             self.current_tab -= 1;
         } else {
-            self.current_tab = 3;
+            self.current_tab = self.tab_count() - 1;
+        }
+    }
+
+    pub fn select_tab(&mut self, tab: usize) {
+        if tab < self.tab_count() {
+            self.current_tab = tab;
+        }
+    }
+
+    fn tab_count(&self) -> usize {
+        TAB_COUNT + self.dashboards.len()
+    }
+
+    /// The user-defined dashboard shown in the current tab, if any.
+    pub fn current_dashboard(&self) -> Option<&Dashboard> {
+        self.dashboards.get(self.current_tab.checked_sub(TAB_COUNT)?)
+    }
+
+    /// Selects row `index` of the current tab's table, ignoring rows past the end.
+    pub fn select_row(&mut self, index: usize) {
+        match self.current_tab {
+            SERVICES_TAB if index < self.services.len() => self.selected_service = index,
+            HOSTS_TAB if index < self.hosts.len() && index != self.selected_host => {
+                self.selected_host = index;
+                self.switch_host();
+            }
+            PROCESSES_TAB if index < self.visible_processes().len() => self.selected_process = index,
+            NETWORK_TAB if index < self.visible_connections().len() => self.selected_connection = index,
+            SENSORS_TAB if index < self.sensors.len() => self.selected_sensor = index,
+            _ => {}
+        }
+    }
+
+    /// Moves the selection by `delta` rows, stopping at either end rather than wrapping.
+    pub fn move_row(&mut self, delta: isize) {
+        if self.current_tab == LOGS_TAB {
+            self.scroll_logs(delta);
+            return;
+        }
+        let (selected, len) = match self.current_tab {
+            SERVICES_TAB => (self.selected_service, self.services.len()),
+            HOSTS_TAB => (self.selected_host, self.hosts.len()),
+            NETWORK_TAB => (self.selected_connection, self.visible_connections().len()),
+            SENSORS_TAB => (self.selected_sensor, self.sensors.len()),
+            _ => (self.selected_process, self.visible_processes().len()),
+        };
+        if len > 0 {
+            self.select_row(selected.saturating_add_signed(delta).min(len - 1));
+        }
+    }
+
+    /// Rows that fit in the current tab's table, as of the last frame.
+    fn page_size(&self) -> isize {
+        let height = match self.current_tab {
+            SERVICES_TAB => &self.service_view.height,
+            HOSTS_TAB => &self.host_view.height,
+            LOGS_TAB => &self.log_view.height,
+            NETWORK_TAB => &self.connection_view.height,
+            SENSORS_TAB => &self.sensor_view.height,
+            _ => &self.process_view.height,
+        };
+        height.get().max(1) as isize
+    }
+
+    pub fn page_down(&mut self) {
+        self.move_row(self.page_size());
+    }
+
+    pub fn page_up(&mut self) {
+        self.move_row(-self.page_size());
+    }
+
+    pub fn half_page_down(&mut self) {
+        self.move_row((self.page_size() / 2).max(1));
+    }
+
+    pub fn half_page_up(&mut self) {
+        self.move_row(-(self.page_size() / 2).max(1));
+    }
+
+    pub fn first_row(&mut self) {
+        self.move_row(isize::MIN);
+    }
+
+    pub fn last_row(&mut self) {
+        if self.current_tab == LOGS_TAB {
+            // Jumping to the end of the log also resumes following it
+            self.log_view.paused_at = None;
+        }
+        self.move_row(isize::MAX);
+    }
+
+    pub fn next_row(&mut self) {
+        match self.current_tab {
+            SERVICES_TAB => self.next_service(),
+            HOSTS_TAB => self.next_host(),
+            LOGS_TAB | NETWORK_TAB | SENSORS_TAB => self.move_row(1),
+            _ => self.next_process(),
+        }
+    }
+
+    pub fn previous_row(&mut self) {
+        match self.current_tab {
+            SERVICES_TAB => self.previous_service(),
+            HOSTS_TAB => self.previous_host(),
+            LOGS_TAB | NETWORK_TAB | SENSORS_TAB => self.move_row(-1),
+            _ => self.previous_process(),
         }
     }
 
     pub fn next_process(&mut self) {
-        if !self.processes.is_empty() {
-            self.selected_process = (self.selected_process + 1) % self.processes.len();
+        let count = self.visible_processes().len();
+        if count > 0 {
+            self.selected_process = (self.selected_process + 1) % count;
         }
     }
 
     pub fn previous_process(&mut self) {
-        if !self.processes.is_empty() {
+        let count = self.visible_processes().len();
+        if count > 0 {
             if self.selected_process > 0 {
                 self.selected_process -= 1;
             } else {
-                self.selected_process = self.processes.len() - 1;
+                self.selected_process = count - 1;
+            }
+        }
+    }
+
+    /// Processes shown in the Processes tab, in display order.
+    pub fn visible_processes(&self) -> Vec<&ProcessInfo> {
+        self.processes.iter().filter(|p| !self.problem_filter || p.status.is_problem()).collect()
+    }
+
+    pub fn selected_process_info(&self) -> Option<&ProcessInfo> {
+        self.visible_processes().get(self.selected_process).copied()
+    }
+
+    /// Selects the process with `pid` if it is shown.
+    fn select_pid(&mut self, pid: u32) -> bool {
+        match self.visible_processes().iter().position(|p| p.pid == pid) {
+            Some(index) => {
+                self.selected_process = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn clamp_process_selection(&mut self) {
+        let count = self.visible_processes().len();
+        if self.selected_process >= count {
+            self.selected_process = count.saturating_sub(1);
+        }
+    }
+
+    pub fn state_counts(&self) -> StateCounts {
+        self.zombies.counts(&self.processes)
+    }
+
+    /// Shows only zombie, D-state and stopped processes, or everything again.
+    pub fn toggle_problem_filter(&mut self) {
+        if self.current_tab != PROCESSES_TAB {
+            return;
+        }
+        // Keep the same process selected when it stays visible
+        let pid = self.selected_process_info().map(|p| p.pid);
+        self.problem_filter = !self.problem_filter;
+        if !pid.is_some_and(|pid| self.select_pid(pid)) {
+            self.selected_process = 0;
+        }
+    }
+
+    pub fn next_service(&mut self) {
+        if !self.services.is_empty() {
+            self.selected_service = (self.selected_service + 1) % self.services.len();
+        }
+    }
+
+    pub fn previous_service(&mut self) {
+        if !self.services.is_empty() {
+            if self.selected_service > 0 {
+                self.selected_service -= 1;
+            } else {
+                self.selected_service = self.services.len() - 1;
+            }
+        }
+    }
+
+    pub fn next_host(&mut self) {
+        if !self.hosts.is_empty() {
+            self.selected_host = (self.selected_host + 1) % self.hosts.len();
+            self.switch_host();
+        }
+    }
+
+    pub fn previous_host(&mut self) {
+        if !self.hosts.is_empty() {
+            if self.selected_host > 0 {
+                self.selected_host -= 1;
+            } else {
+                self.selected_host = self.hosts.len() - 1;
+            }
+            self.switch_host();
+        }
+    }
+
+    fn switch_host(&mut self) {
+        self.processes.clear();
+        self.process_events.clear();
+        self.watch.reset();
+        self.services.clear();
+        self.cpu_history.clear();
+        self.memory_history.clear();
+        self.swap_history.clear();
+        self.memory_breakdown = None;
+        self.pressure = None;
+        self.pressure_history.clear();
+        self.cpu_stats = None;
+        self.cpu_breakdown_history.clear();
+        self.network_history.clear();
+        self.disk_usage.clear();
+        self.remote_info = None;
+        self.apply_remote_snapshot();
+    }
+
+    /// Scrolls the log by `delta` lines, positive towards the newest. Scrolling
+    /// back pauses the log so the lines being read stay in place.
+    fn scroll_logs(&mut self, delta: isize) {
+        let count = self.logs.visible(&self.log_view).count();
+        let max_scroll = count.saturating_sub(self.log_view.height.get()) as isize;
+        let scroll = (self.log_view.scroll as isize).saturating_sub(delta).clamp(0, max_scroll.max(0));
+        if scroll > 0 && self.log_view.paused_at.is_none() {
+            self.log_view.paused_at = self.logs.latest_seq();
+        }
+        self.log_view.scroll = scroll as usize;
+    }
+
+    pub fn toggle_log_pause(&mut self) {
+        if self.current_tab != LOGS_TAB {
+            return;
+        }
+        self.log_view.paused_at = match self.log_view.paused_at {
+            Some(_) => None,
+            None => self.logs.latest_seq(),
+        };
+        self.log_view.scroll = 0;
+    }
+
+    pub fn cycle_log_severity(&mut self) {
+        if self.current_tab == LOGS_TAB {
+            self.log_view.min_severity = self.log_view.min_severity.cycle();
+            self.log_view.scroll = 0;
+        }
+    }
+
+    /// Connections passing the state and port filters, in table order.
+    pub fn visible_connections(&self) -> Vec<&Connection> {
+        self.connections.iter().filter(|c| self.connection_filter.matches(c)).collect()
+    }
+
+    fn clamp_sensor_selection(&mut self) {
+        if self.selected_sensor >= self.sensors.len() {
+            self.selected_sensor = self.sensors.len().saturating_sub(1);
+        }
+    }
+
+    fn clamp_connection_selection(&mut self) {
+        let count = self.visible_connections().len();
+        if self.selected_connection >= count {
+            self.selected_connection = count.saturating_sub(1);
+        }
+    }
+
+    pub fn visible_interfaces(&self) -> Vec<&Interface> {
+        self.interfaces.iter().filter(|i| self.show_virtual_interfaces || !i.is_virtual).collect()
+    }
+
+    pub fn toggle_virtual_interfaces(&mut self) {
+        if self.current_tab == NETWORK_TAB {
+            self.show_virtual_interfaces = !self.show_virtual_interfaces;
+        }
+    }
+
+    pub fn cycle_connection_state(&mut self) {
+        if self.current_tab == NETWORK_TAB {
+            self.connection_filter.cycle_state();
+            self.clamp_connection_selection();
+        }
+    }
+
+    /// Whether typed keys go to a filter rather than acting as shortcuts.
+    pub fn is_editing_filter(&self) -> bool {
+        self.log_view.editing.is_some() || self.connection_filter.editing.is_some()
+    }
+
+    /// Starts editing the text filter on the Logs tab or the port filter on the Network tab.
+    pub fn start_filter(&mut self) {
+        match self.current_tab {
+            LOGS_TAB => self.log_view.editing = Some(self.log_view.filter.clone()),
+            NETWORK_TAB => {
+                self.connection_filter.editing =
+                    Some(self.connection_filter.port.map(|port| port.to_string()).unwrap_or_default());
+            }
+            _ => {}
+        }
+    }
+
+    pub fn push_filter(&mut self, c: char) {
+        if let Some(editing) = &mut self.log_view.editing {
+            editing.push(c);
+            self.log_view.scroll = 0;
+        } else if let Some(editing) = &mut self.connection_filter.editing {
+            // Anything longer than five digits cannot be a port
+            if c.is_ascii_digit() && editing.len() < 5 {
+                editing.push(c);
+            }
+            self.clamp_connection_selection();
+        }
+    }
+
+    pub fn pop_filter(&mut self) {
+        if let Some(editing) = &mut self.log_view.editing {
+            editing.pop();
+            self.log_view.scroll = 0;
+        } else if let Some(editing) = &mut self.connection_filter.editing {
+            editing.pop();
+            self.clamp_connection_selection();
+        }
+    }
+
+    pub fn apply_filter(&mut self) {
+        if let Some(filter) = self.log_view.editing.take() {
+            self.log_view.filter = filter;
+        }
+        if self.connection_filter.editing.is_some() {
+            self.connection_filter.port = self.connection_filter.port();
+            self.connection_filter.editing = None;
+        }
+    }
+
+    pub fn cancel_filter(&mut self) {
+        if self.log_view.editing.take().is_some() {
+            self.log_view.scroll = 0;
+        }
+        if self.connection_filter.editing.take().is_some() {
+            self.clamp_connection_selection();
+        }
+    }
+
+    /// Opens the full tab set for the host selected on the dashboard.
+    pub fn open_selected_host(&mut self) {
+        if self.current_tab == HOSTS_TAB {
+            self.current_tab = OVERVIEW_TAB;
+        }
+    }
+
+    /// Summarises every monitored host for the dashboard, in `hosts` order.
+    pub fn host_summaries(&self) -> Vec<HostSummary> {
+        if !self.is_remote() {
+            let info = self.get_system_info();
+            return vec![HostSummary::new(&info, &self.processes, &self.cpu_history, &self.disk_usage, self.cpu_mode)];
+        }
+
+        self.hosts
+            .iter()
+            .map(|host| {
+                let host = host.lock().unwrap();
+                match &host.snapshot {
+                    Some(snapshot) => {
+                        let mut summary = HostSummary::new(
+                            &snapshot.system_info,
+                            &snapshot.processes,
+                            &snapshot.cpu_history,
+                            &snapshot.disk_usage,
+                            self.cpu_mode,
+                        );
+                        summary.source = host.address.clone();
+                        summary
+                    }
+                    None => HostSummary::new(&SystemInfo::unavailable(&host.address), &[], &[], &[], self.cpu_mode),
+                }
+            })
+            .collect()
+    }
+
+    /// Watches or unwatches the selected process by name and saves the watch list.
+    pub fn toggle_watch_selected(&mut self) {
+        if self.current_tab != PROCESSES_TAB {
+            return;
+        }
+        let Some((pid, name)) = self.selected_process_info().map(|p| (p.pid, p.name.clone())) else {
+            return;
+        };
+        let watched = self.watch.toggle(&name);
+        self.sort_processes();
+        self.select_pid(pid);
+
+        let verb = if watched { "watching" } else { "stopped watching" };
+        self.status_message = Some(match &self.config_path {
+            Some(path) => match Config::save_watch_list(path, &self.watch.patterns) {
+                Ok(()) => format!("{} {} (saved to {})", verb, name, path.display()),
+                Err(err) => format!("{} {} (not saved: {:#})", verb, name, err),
+            },
+            None => format!("{} {} (not saved: no config path)", verb, name),
+        });
+    }
+
+    /// Jumps between a process and the systemd unit that owns it.
+    pub fn jump_to_unit_or_process(&mut self) {
+        match self.current_tab {
+            PROCESSES_TAB => {
+                let Some(process) = self.selected_process_info() else {
+                    return;
+                };
+                if let Some(index) = self.services.iter().position(|s| s.pids.contains(&process.pid)) {
+                    self.selected_service = index;
+                    self.current_tab = SERVICES_TAB;
+                }
             }
+            SERVICES_TAB => {
+                let Some(pid) = self.services.get(self.selected_service).and_then(|s| s.main_pid) else {
+                    return;
+                };
+                // The main process may be hidden by the state filter
+                if !self.select_pid(pid) {
+                    self.problem_filter = false;
+                    if !self.select_pid(pid) {
+                        return;
+                    }
+                }
+                self.current_tab = PROCESSES_TAB;
+            }
+            _ => {}
         }
     }
 
     pub fn refresh(&mut self) {
+        if self.is_remote() {
+            self.apply_remote_snapshot();
+            return;
+        }
+
         self.system.refresh_all();
         self.networks.refresh();
         self.disks.refresh();
         self.update_processes();
+        self.update_services();
     }
 
     pub fn toggle_sort(&mut self) {
@@ -218,25 +1212,237 @@ impl App {
             SortBy::Pid => SortBy::Name,
             SortBy::Name => SortBy::Cpu,
             SortBy::Cpu => SortBy::Memory,
-            SortBy::Memory => SortBy::Pid,
+            SortBy::Memory => SortBy::Fds,
+            SortBy::Fds => SortBy::Net,
+            SortBy::Net => SortBy::Pid,
         };
         self.sort_processes();
     }
 
+    /// Sorts by `sort_by`, reversing the direction if it is already the sort column.
+    pub fn sort_by_column(&mut self, sort_by: SortBy) {
+        if self.sort_by == sort_by {
+            self.sort_ascending = !self.sort_ascending;
+        } else {
+            self.sort_by = sort_by;
+        }
+        self.sort_processes();
+    }
+
     pub fn kill_selected_process(&mut self) {
-        if !self.processes.is_empty() && self.selected_process < self.processes.len() {
-            let pid = self.processes[self.selected_process].pid;
-            if let Some(process) = self.system.process(Pid::from(pid as usize)) {
-                process.kill();
+        if let Some(process) = self.selected_process_info() {
+            self.dialog = Some(Dialog::Confirm {
+                action: ProcessAction::Kill { pid: process.pid },
+                name: process.name.clone(),
+            });
+        }
+    }
+
+    pub fn stop_selected_process(&mut self) {
+        // Stopping ourselves would freeze the interface with no way to resume it
+        let own_pid = std::process::id();
+        if !self.is_remote() && self.selected_process_info().is_some_and(|p| p.pid == own_pid) {
+            self.status_message = Some("refusing to stop the monitor itself".to_string());
+            return;
+        }
+        self.confirm_signal(|pid| ProcessAction::Stop { pid });
+    }
+
+    pub fn continue_selected_process(&mut self) {
+        self.confirm_signal(|pid| ProcessAction::Continue { pid });
+    }
+
+    fn confirm_signal(&mut self, action: fn(u32) -> ProcessAction) {
+        if self.current_tab != PROCESSES_TAB {
+            return;
+        }
+        if let Some(process) = self.selected_process_info() {
+            self.dialog = Some(Dialog::Confirm { action: action(process.pid), name: process.name.clone() });
+        }
+    }
+
+    pub fn open_renice_dialog(&mut self) {
+        if self.current_tab != PROCESSES_TAB {
+            return;
+        }
+        if let Some(process) = self.selected_process_info() {
+            self.dialog = Some(Dialog::Renice {
+                pid: process.pid,
+                name: process.name.clone(),
+                nice: process.nice.unwrap_or(0),
+            });
+        }
+    }
+
+    pub fn open_ionice_dialog(&mut self) {
+        if self.current_tab != PROCESSES_TAB {
+            return;
+        }
+        if let Some(process) = self.selected_process_info() {
+            let priority = process
+                .io_priority
+                .filter(|p| p.class != IoClass::None)
+                .unwrap_or(IoPriority { class: IoClass::BestEffort, level: 4 });
+            self.dialog = Some(Dialog::Ionice { pid: process.pid, name: process.name.clone(), priority });
+        }
+    }
+
+    /// Lists the selected process's open files. Only local processes can be
+    /// inspected, since the agent protocol carries fd counts but not fd lists.
+    pub fn open_files_dialog(&mut self) {
+        if self.current_tab != PROCESSES_TAB {
+            return;
+        }
+        let Some(process) = self.selected_process_info() else {
+            return;
+        };
+        if let Some(host) = self.hosts.get(self.selected_host) {
+            host.lock().unwrap().last_action = Some("open files can only be listed for local processes".to_string());
+            return;
+        }
+        match fds::list(process.pid) {
+            Ok(files) => {
+                self.dialog = Some(Dialog::OpenFiles { pid: process.pid, name: process.name.clone(), files, scroll: 0 });
+            }
+            Err(err) => self.status_message = Some(format!("open files of {} ({}): {}", process.name, process.pid, err)),
+        }
+    }
+
+    pub fn open_affinity_dialog(&mut self) {
+        if self.current_tab != PROCESSES_TAB {
+            return;
+        }
+        let cpu_count = self.cpu_count();
+        if let Some(process) = self.selected_process_info() {
+            let cores = (0..cpu_count)
+                .map(|cpu| process.affinity.as_ref().is_none_or(|allowed| allowed.contains(&cpu)))
+                .collect();
+            self.dialog = Some(Dialog::Affinity { pid: process.pid, name: process.name.clone(), cores, cursor: 0 });
+        }
+    }
+
+    /// Raises the value under edit: nice, io priority level, or nothing.
+    pub fn dialog_up(&mut self) {
+        match &mut self.dialog {
+            Some(Dialog::Renice { nice, .. }) => *nice = (*nice + 1).min(19),
+            Some(Dialog::Ionice { priority, .. }) => priority.level = priority.level.saturating_sub(1),
+            Some(Dialog::OpenFiles { scroll, .. }) => *scroll = scroll.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    pub fn dialog_down(&mut self) {
+        match &mut self.dialog {
+            Some(Dialog::Renice { nice, .. }) => *nice = (*nice - 1).max(-20),
+            Some(Dialog::Ionice { priority, .. }) => priority.level = (priority.level + 1).min(7),
+            Some(Dialog::OpenFiles { files, scroll, .. }) => *scroll = (*scroll + 1).min(files.len().saturating_sub(1)),
+            _ => {}
+        }
+    }
+
+    /// Moves between io classes or affinity checkboxes.
+    pub fn dialog_left(&mut self) {
+        match &mut self.dialog {
+            Some(Dialog::Ionice { priority, .. }) => {
+                priority.class = match priority.class {
+                    IoClass::RealTime => IoClass::Idle,
+                    IoClass::BestEffort => IoClass::RealTime,
+                    IoClass::Idle | IoClass::None => IoClass::BestEffort,
+                }
+            }
+            Some(Dialog::Affinity { cursor, cores, .. }) => {
+                *cursor = if *cursor == 0 { cores.len().saturating_sub(1) } else { *cursor - 1 };
             }
+            _ => {}
         }
     }
 
+    pub fn dialog_right(&mut self) {
+        match &mut self.dialog {
+            Some(Dialog::Ionice { priority, .. }) => {
+                priority.class = match priority.class {
+                    IoClass::RealTime => IoClass::BestEffort,
+                    IoClass::BestEffort | IoClass::None => IoClass::Idle,
+                    IoClass::Idle => IoClass::RealTime,
+                }
+            }
+            Some(Dialog::Affinity { cursor, cores, .. }) => {
+                *cursor = (*cursor + 1) % cores.len().max(1);
+            }
+            _ => {}
+        }
+    }
+
+    pub fn dialog_toggle(&mut self) {
+        if let Some(Dialog::Affinity { cores, cursor, .. }) = &mut self.dialog {
+            if let Some(core) = cores.get_mut(*cursor) {
+                *core = !*core;
+            }
+        }
+    }
+
+    /// Moves an editing dialog on to confirmation, or performs a confirmed action.
+    pub fn dialog_submit(&mut self) {
+        let Some(dialog) = self.dialog.take() else {
+            return;
+        };
+        self.dialog = match dialog {
+            Dialog::Renice { pid, name, nice } => Some(Dialog::Confirm { action: ProcessAction::Renice { pid, nice }, name }),
+            Dialog::Ionice { pid, name, priority } => {
+                Some(Dialog::Confirm { action: ProcessAction::Ionice { pid, priority }, name })
+            }
+            Dialog::Affinity { pid, name, cores, .. } => {
+                let cores = cores.iter().enumerate().filter(|(_, &on)| on).map(|(cpu, _)| cpu).collect();
+                Some(Dialog::Confirm { action: ProcessAction::Affinity { pid, cores }, name })
+            }
+            Dialog::Confirm { action, name } => {
+                self.run_action(action, &name);
+                None
+            }
+            Dialog::OpenFiles { .. } => None,
+        };
+    }
+
+    pub fn dialog_cancel(&mut self) {
+        self.dialog = None;
+    }
+
+    fn run_action(&mut self, action: ProcessAction, name: &str) {
+        // Pids from a remote host must never be resolved against this machine.
+        if let Some(host) = self.hosts.get(self.selected_host) {
+            let mut host = host.lock().unwrap();
+            host.last_action = Some(format!("sent: {} ({})", action.describe(), name));
+            host.send_action(ClientMessage::Action(action));
+            return;
+        }
+
+        self.status_message = Some(match process_control::execute(&action) {
+            Ok(result) => format!("{} ({}): {}", action.describe(), name, result),
+            Err(err) => format!("failed to {} ({}): {}", action.describe(), name, err),
+        });
+        // The next tick shows the effect; refreshing here would skew the CPU sampling interval
+    }
+
     pub fn get_system_info(&self) -> SystemInfo {
+        if let Some((info, _)) = &self.remote_info {
+            return info.clone();
+        }
+        if let Some(host) = self.hosts.get(self.selected_host) {
+            return SystemInfo::unavailable(&host.lock().unwrap().address);
+        }
+
+        let (used_memory, total_memory) = self.memory_totals();
+        let container = self.container_limits().map(|limits| ContainerInfo {
+            memory_limit: limits.memory_max,
+            cpu_limit: limits.cpu_quota,
+            host_total_memory: self.system.total_memory(),
+            host_cpu_count: self.system.cpus().len(),
+        });
+
         SystemInfo {
             cpu_count: self.system.cpus().len(),
-            total_memory: self.system.total_memory(),
-            used_memory: self.system.used_memory(),
+            total_memory,
+            used_memory,
             total_swap: self.system.total_swap(),
             used_swap: self.system.used_swap(),
             system_name: System::name().unwrap_or_else(|| "Unknown".to_string()),
@@ -244,11 +1450,14 @@ impl App {
             os_version: System::os_version().unwrap_or_else(|| "Unknown".to_string()),
             host_name: System::host_name().unwrap_or_else(|| "Unknown".to_string()),
             uptime: System::uptime(),
+            load_average: get_load_average(),
+            source: "local".to_string(),
+            container,
         }
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemInfo {
     pub cpu_count: usize,
     pub total_memory: u64,
@@ -260,4 +1469,80 @@ pub struct SystemInfo {
     pub os_version: String,
     pub host_name: String,
     pub uptime: u64,
+    pub load_average: Option<(f64, f64, f64)>,
+    pub source: String, // "local" or the agent address
+    pub container: Option<ContainerInfo>,
+}
+
+impl SystemInfo {
+    /// Placeholder shown for a remote host that has not sent a snapshot yet.
+    pub fn unavailable(host_name: &str) -> Self {
+        let unknown = || "Unknown".to_string();
+        Self {
+            cpu_count: 0,
+            total_memory: 0,
+            used_memory: 0,
+            total_swap: 0,
+            used_swap: 0,
+            system_name: unknown(),
+            kernel_version: unknown(),
+            os_version: unknown(),
+            host_name: host_name.to_string(),
+            uptime: 0,
+            load_average: None,
+            source: host_name.to_string(),
+            container: None,
+        }
+    }
+}
+
+/// One row of the multi-host dashboard.
+#[derive(Debug, Clone)]
+pub struct HostSummary {
+    pub source: String,
+    pub host_name: String,
+    pub cpu_usage: Option<f32>,
+    pub memory_percent: Option<f32>,
+    pub load_average: Option<(f64, f64, f64)>,
+    pub top_process: Option<(String, f32)>, // (name, cpu%)
+    pub fullest_disk: Option<(String, f32)>, // (name, usage%)
+}
+
+impl HostSummary {
+    pub fn new(
+        info: &SystemInfo,
+        processes: &[ProcessInfo],
+        cpu_history: &[f32],
+        disk_usage: &[(String, u64, u64)],
+        cpu_mode: CpuMode,
+    ) -> Self {
+        let top_process = processes
+            .iter()
+            .max_by(|a, b| a.cpu_usage.partial_cmp(&b.cpu_usage).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|p| (p.name.clone(), cpu_mode.apply(p.cpu_usage, info.cpu_count)));
+        let fullest_disk = disk_usage
+            .iter()
+            .filter(|(_, _, total)| *total > 0)
+            .map(|(name, used, total)| (name.clone(), *used as f32 / *total as f32 * 100.0))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        Self {
+            source: info.source.clone(),
+            host_name: info.host_name.clone(),
+            cpu_usage: cpu_history.last().copied(),
+            memory_percent: (info.total_memory > 0)
+                .then(|| info.used_memory as f32 / info.total_memory as f32 * 100.0),
+            load_average: info.load_average,
+            top_process,
+            fullest_disk,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContainerInfo {
+    pub memory_limit: Option<u64>,
+    pub cpu_limit: Option<f64>, // in cores
+    pub host_total_memory: u64,
+    pub host_cpu_count: usize,
 }
\ No newline at end of file
@@ -0,0 +1,247 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::connections;
+use crate::sockets::{self, Socket};
+
+/// Finding socket owners walks every process's fds, so it and the socket tables
+/// are read at most this often. Namespaces of known pids are looked up again as often.
+const OWNERS_REFRESH: Duration = Duration::from_secs(10);
+
+/// How a process's network rate was worked out.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Method {
+    /// Measured on the interfaces of the process's own network namespace, which
+    /// every process in that namespace shares
+    Namespace { processes: usize },
+    /// The host's traffic split by the process's share of the host's
+    /// established non-loopback connections; a rough guess, not a measurement
+    Connections { owned: usize, total: usize },
+}
+
+impl Method {
+    pub fn describe(&self) -> String {
+        match self {
+            Method::Namespace { processes: 1 } => "own network namespace".to_string(),
+            Method::Namespace { processes } => format!("network namespace shared by {} processes", processes),
+            Method::Connections { owned, total } => format!("estimated from {} of {} host connections", owned, total),
+        }
+    }
+}
+
+/// Per-process receive and transmit rates in bytes per second.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Bandwidth {
+    pub received: u64,
+    pub transmitted: u64,
+    pub method: Method,
+}
+
+impl Bandwidth {
+    pub fn total(&self) -> u64 {
+        self.received + self.transmitted
+    }
+}
+
+/// Remembers interface counters between refreshes so they can be turned into rates.
+#[derive(Debug, Default)]
+pub struct Estimator {
+    /// Counters per network namespace inode at the last refresh
+    previous: HashMap<u64, (u64, u64)>,
+    last: Option<Instant>,
+    /// Network namespace of each pid, `None` where unreadable; only changes on exec or setns
+    namespaces: HashMap<u32, Option<u64>>,
+    /// Active connections per owning process, and their total, as of `counted`
+    connections: (HashMap<u32, usize>, usize),
+    counted: Option<Instant>,
+}
+
+impl Estimator {
+    /// Estimates a rate for each of `pids` that can be attributed: processes in
+    /// another network namespace, and processes in ours holding an active
+    /// connection. Nothing is returned on the first call, which only records the counters.
+    pub fn estimate(&mut self, pids: &[u32]) -> HashMap<u32, Bandwidth> {
+        let now = Instant::now();
+        let elapsed = self.last.map(|last| now.duration_since(last).as_secs_f64());
+        self.last = Some(now);
+
+        let refresh = self.counted.is_none_or(|counted| now.duration_since(counted) >= OWNERS_REFRESH);
+        if refresh {
+            self.connections = connection_counts(&sockets::read_all(), &connections::socket_owners());
+            self.counted = Some(now);
+        }
+
+        let Some(own) = namespace("self") else {
+            return HashMap::new();
+        };
+        let mut namespaces = HashMap::with_capacity(pids.len());
+        let mut members: HashMap<u64, Vec<u32>> = HashMap::new();
+        for &pid in pids {
+            let ns = match self.namespaces.get(&pid) {
+                Some(ns) if !refresh => *ns,
+                // Unreadable for other users' processes without root
+                _ => namespace(&pid.to_string()),
+            };
+            namespaces.insert(pid, ns);
+            if let Some(ns) = ns {
+                members.entry(ns).or_default().push(pid);
+            }
+        }
+        self.namespaces = namespaces;
+
+        // Read each namespace's counters through one of its processes
+        let mut counters = HashMap::new();
+        if let Some(totals) = interface_totals("self") {
+            counters.insert(own, totals);
+        }
+        for (ns, pids) in &members {
+            if *ns != own {
+                if let Some(totals) = interface_totals(&pids[0].to_string()) {
+                    counters.insert(*ns, totals);
+                }
+            }
+        }
+        let previous = std::mem::replace(&mut self.previous, counters);
+        let Some(elapsed) = elapsed.filter(|elapsed| *elapsed > 0.0) else {
+            return HashMap::new();
+        };
+        let rate = |ns: &u64| {
+            let (received, transmitted) = self.previous.get(ns)?;
+            let (last_received, last_transmitted) = previous.get(ns)?;
+            Some((
+                (received.saturating_sub(*last_received) as f64 / elapsed) as u64,
+                (transmitted.saturating_sub(*last_transmitted) as f64 / elapsed) as u64,
+            ))
+        };
+
+        let mut rates = HashMap::new();
+        for (ns, pids) in &members {
+            if *ns == own {
+                continue;
+            }
+            if let Some((received, transmitted)) = rate(ns) {
+                let method = Method::Namespace { processes: pids.len() };
+                for &pid in pids {
+                    rates.insert(pid, Bandwidth { received, transmitted, method });
+                }
+            }
+        }
+
+        // Processes sharing our namespace get a slice of its traffic
+        let Some((received, transmitted)) = rate(&own) else {
+            return rates;
+        };
+        let (owned, total) = &self.connections;
+        let total = *total;
+        if total == 0 {
+            return rates;
+        }
+        for pid in members.get(&own).into_iter().flatten() {
+            // Without a connection there is nothing to base a guess on
+            let Some(&owned) = owned.get(pid) else {
+                continue;
+            };
+            let share = owned as f64 / total as f64;
+            rates.insert(*pid, Bandwidth {
+                received: (received as f64 * share) as u64,
+                transmitted: (transmitted as f64 * share) as u64,
+                method: Method::Connections { owned, total },
+            });
+        }
+        rates
+    }
+}
+
+/// Inode of the network namespace of `/proc/<pid>`, from the `net:[inode]` link.
+fn namespace(pid: &str) -> Option<u64> {
+    let link = fs::read_link(format!("/proc/{}/ns/net", pid)).ok()?;
+    link.to_str()?.strip_prefix("net:[")?.strip_suffix(']')?.parse().ok()
+}
+
+/// Bytes received and transmitted on every interface but loopback, as seen
+/// from `/proc/<pid>/net/dev`.
+fn interface_totals(pid: &str) -> Option<(u64, u64)> {
+    fs::read_to_string(format!("/proc/{}/net/dev", pid)).ok().map(|contents| parse_net_dev(&contents))
+}
+
+fn parse_net_dev(contents: &str) -> (u64, u64) {
+    let mut totals = (0, 0);
+    // Two header lines, then `iface: rx_bytes packets errs drop fifo frame compressed multicast tx_bytes ...`
+    for line in contents.lines().skip(2) {
+        let Some((name, counters)) = line.split_once(':') else {
+            continue;
+        };
+        if name.trim() == "lo" {
+            continue;
+        }
+        let fields: Vec<u64> = counters.split_whitespace().filter_map(|field| field.parse().ok()).collect();
+        if fields.len() >= 9 {
+            totals.0 += fields[0];
+            totals.1 += fields[8];
+        }
+    }
+    totals
+}
+
+/// Established TCP and connected UDP sockets with a non-loopback peer, per
+/// owning process, and their total including sockets with no known owner.
+fn connection_counts(sockets: &[Socket], owners: &HashMap<u64, u32>) -> (HashMap<u32, usize>, usize) {
+    let active: Vec<u64> = sockets
+        .iter()
+        .filter(|socket| matches!(socket.state, "ESTABLISHED" | "CONNECTED") && !socket.remote.ip().to_canonical().is_loopback())
+        .map(|socket| socket.inode)
+        .collect();
+    let mut counts = HashMap::new();
+    for inode in &active {
+        if let Some(pid) = owners.get(inode) {
+            *counts.entry(*pid).or_insert(0) += 1;
+        }
+    }
+    (counts, active.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sockets::Protocol;
+
+    #[test]
+    fn sums_non_loopback_interfaces() {
+        let contents = "\
+Inter-|   Receive                                                |  Transmit
+ face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+    lo:  500000    4000    0    0    0     0          0         0   500000    4000    0    0    0     0       0          0
+  eth0: 1000000    8000    0    0    0     0          0         0   250000    2000    0    0    0     0       0          0
+wlan0:    2000      20    0    0    0     0          0         0     3000      30    0    0    0     0       0          0
+";
+        assert_eq!(parse_net_dev(contents), (1002000, 253000));
+        assert_eq!(parse_net_dev(""), (0, 0));
+    }
+
+    fn socket(protocol: Protocol, remote: &str, state: &'static str, inode: u64) -> Socket {
+        Socket { protocol, local: "10.0.0.2:40000".parse().unwrap(), remote: remote.parse().unwrap(), state, inode }
+    }
+
+    #[test]
+    fn counts_active_remote_connections_per_owner() {
+        let sockets = [
+            socket(Protocol::Tcp, "93.184.216.34:443", "ESTABLISHED", 1),
+            socket(Protocol::Tcp, "93.184.216.34:443", "ESTABLISHED", 2),
+            socket(Protocol::Udp, "8.8.8.8:53", "CONNECTED", 3),
+            // Unowned, but still part of the host's traffic
+            socket(Protocol::Tcp, "1.1.1.1:443", "ESTABLISHED", 4),
+            // Loopback, listening and closing sockets carry no outside traffic
+            socket(Protocol::Tcp, "127.0.0.1:5432", "ESTABLISHED", 5),
+            socket(Protocol::Tcp, "[::ffff:127.0.0.1]:5432", "ESTABLISHED", 6),
+            socket(Protocol::Tcp, "0.0.0.0:0", "LISTEN", 7),
+            socket(Protocol::Tcp, "93.184.216.34:443", "TIME_WAIT", 8),
+        ];
+        let owners = HashMap::from([(1, 100), (2, 100), (3, 200), (5, 300), (6, 300), (7, 300)]);
+
+        let (owned, total) = connection_counts(&sockets, &owners);
+        assert_eq!(total, 4);
+        assert_eq!(owned, HashMap::from([(100, 2), (200, 1)]));
+    }
+}
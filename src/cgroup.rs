@@ -0,0 +1,160 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Which resource totals the gauges are measured against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Perspective {
+    /// Use container limits when a cgroup v2 quota is detected
+    Auto,
+    /// Always report host totals
+    Host,
+    /// Report container limits, falling back to host totals when none are set
+    Container,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CgroupLimits {
+    pub memory_max: Option<u64>,
+    pub memory_current: Option<u64>,
+    pub cpu_quota: Option<f64>, // in cores
+    pub cpu_usage_usec: Option<u64>,
+    pub path: PathBuf,
+}
+
+impl CgroupLimits {
+    pub fn is_limited(&self) -> bool {
+        self.memory_max.is_some() || self.cpu_quota.is_some()
+    }
+}
+
+/// Reads the cgroup v2 limits that apply to this process, or `None` when no
+/// unified hierarchy is mounted.
+pub fn detect() -> Option<CgroupLimits> {
+    let mount = unified_mount()?;
+    let self_cgroup = fs::read_to_string("/proc/self/cgroup").ok()?;
+    let relative = parse_unified_path(&self_cgroup)?;
+    Some(read_limits(mount, &relative))
+}
+
+/// Locates the cgroup v2 hierarchy, which hybrid setups mount under `unified`.
+pub fn unified_mount() -> Option<&'static Path> {
+    ["/sys/fs/cgroup", "/sys/fs/cgroup/unified"]
+        .into_iter()
+        .map(Path::new)
+        .find(|p| p.join("cgroup.controllers").exists())
+}
+
+/// Extracts the path of the `0::` entry from `/proc/<pid>/cgroup`.
+pub fn parse_unified_path(contents: &str) -> Option<String> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(|path| path.trim().to_string())
+}
+
+/// Reads limits for the cgroup at `relative` below `mount`. Limits on parent
+/// cgroups also constrain the leaf, so the tightest one along the path wins.
+pub fn read_limits(mount: &Path, relative: &str) -> CgroupLimits {
+    let leaf = mount.join(relative.trim_start_matches('/'));
+    let mut limits = CgroupLimits {
+        memory_current: read_u64(&leaf.join("memory.current")),
+        cpu_usage_usec: read_cpu_usage(&leaf),
+        path: leaf.clone(),
+        ..Default::default()
+    };
+
+    let mut dir: Option<PathBuf> = Some(leaf);
+    while let Some(current) = dir {
+        if let Some(max) = fs::read_to_string(current.join("memory.max"))
+            .ok()
+            .and_then(|s| parse_memory_max(&s))
+        {
+            limits.memory_max = Some(limits.memory_max.map_or(max, |m| m.min(max)));
+        }
+        if let Some(quota) = fs::read_to_string(current.join("cpu.max"))
+            .ok()
+            .and_then(|s| parse_cpu_max(&s))
+        {
+            limits.cpu_quota = Some(limits.cpu_quota.map_or(quota, |q| q.min(quota)));
+        }
+
+        if current == mount {
+            break;
+        }
+        dir = current.parent().map(Path::to_path_buf);
+    }
+
+    limits
+}
+
+/// Parses `memory.max`; `max` means unlimited.
+pub fn parse_memory_max(contents: &str) -> Option<u64> {
+    match contents.trim() {
+        "max" => None,
+        value => value.parse().ok(),
+    }
+}
+
+/// Parses `cpu.max` (`$QUOTA $PERIOD`) into a number of cores.
+pub fn parse_cpu_max(contents: &str) -> Option<f64> {
+    let mut parts = contents.split_whitespace();
+    let quota = parts.next()?;
+    let period: f64 = parts.next().unwrap_or("100000").parse().ok()?;
+    if quota == "max" || period <= 0.0 {
+        return None;
+    }
+    Some(quota.parse::<f64>().ok()? / period)
+}
+
+fn read_cpu_usage(dir: &Path) -> Option<u64> {
+    let stat = fs::read_to_string(dir.join("cpu.stat")).ok()?;
+    stat.lines()
+        .find_map(|line| line.strip_prefix("usage_usec "))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_memory_max() {
+        assert_eq!(parse_memory_max("max\n"), None);
+        assert_eq!(parse_memory_max("536870912\n"), Some(536870912));
+        assert_eq!(parse_memory_max(""), None);
+    }
+
+    #[test]
+    fn parses_cpu_max() {
+        assert_eq!(parse_cpu_max("max 100000\n"), None);
+        assert_eq!(parse_cpu_max("max\n"), None);
+        assert_eq!(parse_cpu_max("150000 100000\n"), Some(1.5));
+        // The period defaults to 100ms when left out
+        assert_eq!(parse_cpu_max("50000"), Some(0.5));
+        assert_eq!(parse_cpu_max("50000 0"), None);
+        assert_eq!(parse_cpu_max(""), None);
+    }
+
+    #[test]
+    fn parses_unified_path() {
+        assert_eq!(parse_unified_path("0::/\n").as_deref(), Some("/"));
+        assert_eq!(
+            parse_unified_path("0::/system.slice/docker-abc.scope\n").as_deref(),
+            Some("/system.slice/docker-abc.scope")
+        );
+        // Hybrid hierarchy: v1 controllers alongside the unified entry
+        let hybrid = "\
+12:memory:/user.slice
+11:cpu,cpuacct:/user.slice
+1:name=systemd:/user.slice/session-2.scope
+0::/user.slice/session-2.scope
+";
+        assert_eq!(parse_unified_path(hybrid).as_deref(), Some("/user.slice/session-2.scope"));
+        // Pure cgroup v1 has no unified entry
+        assert_eq!(parse_unified_path("4:memory:/\n3:cpu,cpuacct:/\n"), None);
+    }
+}
@@ -0,0 +1,115 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::alerts::AlertRule;
+use crate::dashboard::DashboardConfig;
+use crate::logs::LogsConfig;
+use crate::plugins::PluginConfig;
+
+/// Settings read from `config.toml`. Every section is optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub agent: AgentConfig,
+    pub client: ClientConfig,
+    pub alerts: Vec<AlertRule>,
+    /// Process name patterns pinned to the top of the Processes tab; `*` matches any run of characters
+    pub watch: Vec<String>,
+    /// Extra tabs laid out from the built-in widgets
+    pub dashboards: Vec<DashboardConfig>,
+    /// External programs that report custom metrics as JSON lines
+    pub plugins: Vec<PluginConfig>,
+    pub logs: LogsConfig,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgentConfig {
+    /// Pre-shared token clients must present before receiving data
+    pub token: Option<String>,
+    /// Lets authenticated clients act on processes, e.g. kill them
+    pub allow_control: bool,
+    /// File that remote actions and authentication failures are appended to
+    pub audit_log: Option<PathBuf>,
+    pub tls: Option<AgentTlsConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AgentTlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+    /// CA bundle for client certificates; setting it enables mutual TLS
+    pub client_ca: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ClientConfig {
+    pub token: Option<String>,
+    pub tls: Option<ClientTlsConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientTlsConfig {
+    /// CA bundle used to verify the agent's certificate
+    pub ca: PathBuf,
+    /// Client certificate and key presented to agents that require mutual TLS
+    pub cert: Option<PathBuf>,
+    pub key: Option<PathBuf>,
+    /// Name to verify the agent certificate against instead of the host in `--connect`
+    pub server_name: Option<String>,
+}
+
+impl Config {
+    /// Loads `path`, or the default location when no path is given. Only an
+    /// explicitly requested file is required to exist.
+    pub fn load(path: Option<&Path>) -> Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match default_path() {
+                Some(path) if path.exists() => path,
+                _ => return Ok(Self::default()),
+            },
+        };
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        toml::from_str(&contents).with_context(|| format!("invalid config file {}", path.display()))
+    }
+
+    /// The file `load` reads, or would create when saving: `path` if given, else the default location.
+    pub fn path(path: Option<&Path>) -> Option<PathBuf> {
+        path.map(Path::to_path_buf).or_else(default_path)
+    }
+
+    /// Replaces the `watch` list in the config file at `path`, creating the file if
+    /// needed. The rest of the file, including comments, is left as it was.
+    pub fn save_watch_list(path: &Path, patterns: &[String]) -> Result<()> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err).with_context(|| format!("failed to read config file {}", path.display())),
+        };
+        let mut document: toml_edit::DocumentMut =
+            contents.parse().with_context(|| format!("invalid config file {}", path.display()))?;
+        document["watch"] = toml_edit::value(patterns.iter().collect::<toml_edit::Array>());
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        fs::write(path, document.to_string())
+            .with_context(|| format!("failed to write config file {}", path.display()))
+    }
+}
+
+fn default_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))?;
+    Some(base.join("rust-system-monitor").join("config.toml"))
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::fs;
+
+use crate::app::ProcessInfo;
+use crate::sockets;
+
+/// States offered by the state filter, in the order `t` cycles through them.
+pub const STATES: &[&str] = &["LISTEN", "ESTABLISHED", "TIME_WAIT", "CLOSE_WAIT", "SYN_SENT", "UNCONN", "CONNECTED"];
+
+/// A socket in the connections table, with the process holding it if known.
+#[derive(Debug, Clone)]
+pub struct Connection {
+    pub protocol: &'static str,
+    pub local: String,
+    pub remote: String,
+    /// Local and remote ports; unix sockets have neither
+    pub ports: Option<(u16, u16)>,
+    pub state: &'static str,
+    pub pid: Option<u32>,
+    pub process: Option<String>,
+}
+
+impl Connection {
+    /// Bound and waiting for peers: listening TCP and unix sockets, or unconnected UDP ones.
+    pub fn is_listening(&self) -> bool {
+        self.state == "LISTEN" || (self.protocol == "udp" && self.state == "UNCONN")
+    }
+}
+
+/// Reads every TCP, UDP and unix socket and works out which process owns each.
+/// Sockets of processes we cannot inspect (other users, without root) have no owner.
+pub fn collect(processes: &[ProcessInfo]) -> Vec<Connection> {
+    let owners = socket_owners();
+    let owner = |inode: u64| {
+        let pid = owners.get(&inode).copied();
+        let process = pid.and_then(|pid| processes.iter().find(|p| p.pid == pid)).map(|p| p.name.clone());
+        (pid, process)
+    };
+
+    let mut connections: Vec<Connection> = sockets::read_all()
+        .into_iter()
+        .map(|socket| {
+            let (pid, process) = owner(socket.inode);
+            Connection {
+                protocol: match socket.protocol {
+                    sockets::Protocol::Tcp => "tcp",
+                    sockets::Protocol::Udp => "udp",
+                },
+                local: socket.local.to_string(),
+                remote: if socket.remote.ip().is_unspecified() { "*".to_string() } else { socket.remote.to_string() },
+                ports: Some((socket.local.port(), socket.remote.port())),
+                state: socket.state,
+                pid,
+                process,
+            }
+        })
+        .collect();
+    connections.extend(sockets::read_unix().into_iter().map(|socket| {
+        let (pid, process) = owner(socket.inode);
+        Connection {
+            protocol: "unix",
+            local: socket.path.unwrap_or_else(|| format!("[{}] {}", socket.kind, socket.inode)),
+            remote: String::new(),
+            ports: None,
+            state: socket.state,
+            pid,
+            process,
+        }
+    }));
+
+    // Listening sockets first, then by protocol and local port
+    connections.sort_by_key(|c| (!c.is_listening(), c.protocol, c.ports.map(|(local, _)| local)));
+    connections
+}
+
+/// Maps socket inodes to the pid holding them, from the `socket:[inode]` links
+/// under `/proc/<pid>/fd`. Only processes are listed in `/proc`, not threads.
+pub fn socket_owners() -> HashMap<u64, u32> {
+    let mut owners = HashMap::new();
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return owners;
+    };
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            let Ok(link) = fs::read_link(fd.path()) else {
+                continue;
+            };
+            let link = link.to_string_lossy();
+            if let Some(inode) = link.strip_prefix("socket:[").and_then(|rest| rest.strip_suffix(']')) {
+                if let Ok(inode) = inode.parse() {
+                    // A socket shared by several processes goes to the first found, usually the parent
+                    owners.entry(inode).or_insert(pid);
+                }
+            }
+        }
+    }
+    owners
+}
+
+/// State and port filters for the connections table.
+#[derive(Debug, Default)]
+pub struct ConnectionFilter {
+    pub state: Option<&'static str>,
+    pub port: Option<u16>,
+    /// Port being typed, applied as it changes
+    pub editing: Option<String>,
+}
+
+impl ConnectionFilter {
+    pub fn port(&self) -> Option<u16> {
+        match &self.editing {
+            Some(editing) => editing.parse().ok(),
+            None => self.port,
+        }
+    }
+
+    /// Matches sockets in the chosen state with the chosen port at either end.
+    pub fn matches(&self, connection: &Connection) -> bool {
+        self.state.is_none_or(|state| connection.state == state)
+            && self.port().is_none_or(|port| connection.ports.is_some_and(|(local, remote)| local == port || remote == port))
+    }
+
+    /// Moves the state filter on to the next state, then back to showing all.
+    pub fn cycle_state(&mut self) {
+        self.state = match self.state {
+            None => Some(STATES[0]),
+            Some(state) => STATES.iter().skip_while(|s| **s != state).nth(1).copied(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(protocol: &'static str, ports: Option<(u16, u16)>, state: &'static str) -> Connection {
+        Connection {
+            protocol,
+            local: String::new(),
+            remote: String::new(),
+            ports,
+            state,
+            pid: None,
+            process: None,
+        }
+    }
+
+    #[test]
+    fn filters_by_state() {
+        let mut filter = ConnectionFilter::default();
+        let listening = connection("tcp", Some((22, 0)), "LISTEN");
+        let established = connection("tcp", Some((50000, 443)), "ESTABLISHED");
+        assert!(filter.matches(&listening) && filter.matches(&established));
+
+        filter.cycle_state();
+        assert_eq!(filter.state, Some("LISTEN"));
+        assert!(filter.matches(&listening));
+        assert!(!filter.matches(&established));
+    }
+
+    #[test]
+    fn state_filter_cycles_back_to_all() {
+        let mut filter = ConnectionFilter::default();
+        for _ in 0..STATES.len() {
+            filter.cycle_state();
+        }
+        assert_eq!(filter.state, STATES.last().copied());
+        filter.cycle_state();
+        assert_eq!(filter.state, None);
+    }
+
+    #[test]
+    fn filters_by_port_at_either_end() {
+        let filter = ConnectionFilter { port: Some(443), ..Default::default() };
+        assert!(filter.matches(&connection("tcp", Some((50000, 443)), "ESTABLISHED")));
+        assert!(filter.matches(&connection("tcp", Some((443, 0)), "LISTEN")));
+        assert!(!filter.matches(&connection("tcp", Some((22, 0)), "LISTEN")));
+        // Unix sockets have no ports to match
+        assert!(!filter.matches(&connection("unix", None, "CONNECTED")));
+    }
+
+    #[test]
+    fn port_being_typed_takes_over() {
+        let filter = ConnectionFilter { port: Some(443), editing: Some("22".to_string()), ..Default::default() };
+        assert_eq!(filter.port(), Some(22));
+        assert!(filter.matches(&connection("tcp", Some((22, 0)), "LISTEN")));
+    }
+}
@@ -0,0 +1,295 @@
+use anyhow::{bail, Context, Result};
+use ratatui::layout::Constraint;
+use serde::Deserialize;
+
+/// A user-defined tab, configured as `[[dashboards]]` with rows of widgets.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DashboardConfig {
+    pub name: String,
+    pub rows: Vec<RowConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RowConfig {
+    /// Height of the row; see `parse_size`. Rows share the space equally by default.
+    pub size: Option<String>,
+    pub widgets: Vec<WidgetConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WidgetConfig {
+    pub widget: String,
+    /// Width of the widget within its row
+    pub size: Option<String>,
+    /// Number of rows, for `top_processes`
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Widget {
+    SystemInfo,
+    CpuGauge,
+    MemGauge,
+    CpuHistory,
+    MemHistory,
+    SwapHistory,
+    Pressure,
+    PerCoreCpu,
+    TopProcesses { limit: usize },
+    NetRates,
+    DiskTable,
+    ProcessEvents,
+}
+
+const WIDGET_NAMES: &[&str] = &[
+    "system_info",
+    "cpu_gauge",
+    "mem_gauge",
+    "cpu_history",
+    "mem_history",
+    "swap_history",
+    "pressure",
+    "per_core_cpu",
+    "top_processes",
+    "net_rates",
+    "disk_table",
+    "process_events",
+];
+
+impl Widget {
+    fn parse(config: &WidgetConfig) -> Result<Self> {
+        let widget = match config.widget.as_str() {
+            "system_info" => Widget::SystemInfo,
+            "cpu_gauge" => Widget::CpuGauge,
+            "mem_gauge" => Widget::MemGauge,
+            "cpu_history" => Widget::CpuHistory,
+            "mem_history" => Widget::MemHistory,
+            "swap_history" => Widget::SwapHistory,
+            "pressure" => Widget::Pressure,
+            "per_core_cpu" => Widget::PerCoreCpu,
+            "top_processes" => Widget::TopProcesses { limit: config.limit.unwrap_or(10) },
+            "net_rates" => Widget::NetRates,
+            "disk_table" => Widget::DiskTable,
+            "process_events" => Widget::ProcessEvents,
+            other => bail!("unknown widget \"{}\" (expected one of: {})", other, WIDGET_NAMES.join(", ")),
+        };
+        if config.limit.is_some() && !matches!(widget, Widget::TopProcesses { .. }) {
+            bail!("`limit` only applies to top_processes");
+        }
+        if config.limit == Some(0) {
+            bail!("`limit` must be at least 1");
+        }
+        Ok(widget)
+    }
+}
+
+/// A validated dashboard, ready to lay out.
+#[derive(Debug, Clone)]
+pub struct Dashboard {
+    pub name: String,
+    pub rows: Vec<DashboardRow>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DashboardRow {
+    pub height: Constraint,
+    pub widgets: Vec<(Widget, Constraint)>,
+}
+
+/// Validates every configured dashboard. Errors name the dashboard, row and
+/// widget at fault so they can be fixed without guessing.
+pub fn build(configs: &[DashboardConfig], builtin_tabs: &[&str]) -> Result<Vec<Dashboard>> {
+    let mut dashboards: Vec<Dashboard> = Vec::new();
+    for config in configs {
+        let name = config.name.trim();
+        if name.is_empty() {
+            bail!("dashboard names must not be empty");
+        }
+        if builtin_tabs.iter().any(|tab| tab.eq_ignore_ascii_case(name))
+            || dashboards.iter().any(|d| d.name.eq_ignore_ascii_case(name))
+        {
+            bail!("dashboard \"{}\": a tab with this name already exists", name);
+        }
+        let dashboard = build_one(name, config).with_context(|| format!("dashboard \"{}\"", name))?;
+        dashboards.push(dashboard);
+    }
+    Ok(dashboards)
+}
+
+fn build_one(name: &str, config: &DashboardConfig) -> Result<Dashboard> {
+    if config.rows.is_empty() {
+        bail!("no rows defined");
+    }
+    let heights = config
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| parse_size(row.size.as_deref(), config.rows.len()).with_context(|| format!("row {}", i + 1)))
+        .collect::<Result<Vec<_>>>()?;
+    check_percentages(&heights).context("row heights")?;
+
+    let mut rows = Vec::new();
+    for (i, (row, height)) in config.rows.iter().zip(heights).enumerate() {
+        let context = || format!("row {}", i + 1);
+        if row.widgets.is_empty() {
+            bail!("row {}: no widgets defined", i + 1);
+        }
+        let widgets = row
+            .widgets
+            .iter()
+            .enumerate()
+            .map(|(j, widget)| {
+                let parsed = Widget::parse(widget)
+                    .and_then(|w| Ok((w, parse_size(widget.size.as_deref(), row.widgets.len())?)));
+                parsed.with_context(|| format!("widget {} ({})", j + 1, widget.widget))
+            })
+            .collect::<Result<Vec<_>>>()
+            .with_context(context)?;
+        let widths: Vec<Constraint> = widgets.iter().map(|(_, width)| *width).collect();
+        check_percentages(&widths).context("widget widths").with_context(context)?;
+        rows.push(DashboardRow { height, widgets });
+    }
+
+    Ok(Dashboard { name: name.to_string(), rows })
+}
+
+/// Parses a size: `12` is exactly 12 cells, `40%` a share of the space, and
+/// `5+` at least 5 cells. Without a size, `count` siblings split the space equally.
+fn parse_size(size: Option<&str>, count: usize) -> Result<Constraint> {
+    let Some(size) = size.map(str::trim) else {
+        return Ok(Constraint::Ratio(1, count.max(1) as u32));
+    };
+    let number = |digits: &str| {
+        digits
+            .parse::<u16>()
+            .with_context(|| format!("invalid size \"{}\" (expected e.g. \"12\", \"40%\" or \"5+\")", size))
+    };
+    if let Some(percent) = size.strip_suffix('%') {
+        let percent = number(percent)?;
+        if percent == 0 || percent > 100 {
+            bail!("invalid size \"{}\": percentages must be between 1 and 100", size);
+        }
+        Ok(Constraint::Percentage(percent))
+    } else if let Some(minimum) = size.strip_suffix('+') {
+        Ok(Constraint::Min(number(minimum)?))
+    } else {
+        Ok(Constraint::Length(number(size)?))
+    }
+}
+
+fn check_percentages(constraints: &[Constraint]) -> Result<()> {
+    let total: u32 = constraints
+        .iter()
+        .map(|constraint| match constraint {
+            Constraint::Percentage(percent) => *percent as u32,
+            _ => 0,
+        })
+        .sum();
+    if total > 100 {
+        bail!("percentages add up to {}%, more than 100%", total);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn build_from(toml: &str) -> Result<Vec<Dashboard>> {
+        let config: Config = toml::from_str(toml).unwrap();
+        build(&config.dashboards, &["Overview", "Processes"])
+    }
+
+    /// The whole error chain, as `main` prints it.
+    fn error(toml: &str) -> String {
+        format!("{:#}", build_from(toml).unwrap_err())
+    }
+
+    #[test]
+    fn builds_valid_dashboard() {
+        let dashboards = build_from(
+            r#"
+            [[dashboards]]
+            name = "Ops"
+            [[dashboards.rows]]
+            size = "40%"
+            widgets = [{ widget = "cpu_gauge", size = "12" }, { widget = "top_processes", limit = 5, size = "5+" }]
+            "#,
+        )
+        .unwrap();
+        let row = &dashboards[0].rows[0];
+        assert_eq!(row.height, Constraint::Percentage(40));
+        assert_eq!(row.widgets[0], (Widget::CpuGauge, Constraint::Length(12)));
+        assert_eq!(row.widgets[1], (Widget::TopProcesses { limit: 5 }, Constraint::Min(5)));
+    }
+
+    #[test]
+    fn rejects_unknown_widget() {
+        let err = error(
+            r#"
+            [[dashboards]]
+            name = "Ops"
+            rows = [{ widgets = [{ widget = "gpu_gauge" }] }]
+            "#,
+        );
+        assert!(err.starts_with("dashboard \"Ops\": row 1: widget 1 (gpu_gauge): unknown widget \"gpu_gauge\""), "{err}");
+    }
+
+    #[test]
+    fn rejects_names_of_existing_tabs() {
+        let err = error(
+            r#"
+            [[dashboards]]
+            name = "processes"
+            rows = [{ widgets = [{ widget = "cpu_gauge" }] }]
+            "#,
+        );
+        assert_eq!(err, "dashboard \"processes\": a tab with this name already exists");
+
+        let err = error(
+            r#"
+            [[dashboards]]
+            name = "Ops"
+            rows = [{ widgets = [{ widget = "cpu_gauge" }] }]
+            [[dashboards]]
+            name = "ops"
+            rows = [{ widgets = [{ widget = "mem_gauge" }] }]
+            "#,
+        );
+        assert_eq!(err, "dashboard \"ops\": a tab with this name already exists");
+    }
+
+    #[test]
+    fn rejects_zero_and_overflowing_sizes() {
+        let err = error(
+            r#"
+            [[dashboards]]
+            name = "Ops"
+            rows = [{ size = "0%", widgets = [{ widget = "cpu_gauge" }] }]
+            "#,
+        );
+        assert!(err.contains("percentages must be between 1 and 100"), "{err}");
+
+        let err = error(
+            r#"
+            [[dashboards]]
+            name = "Ops"
+            rows = [{ widgets = [{ widget = "cpu_gauge", size = "70000" }] }]
+            "#,
+        );
+        assert!(err.contains("invalid size \"70000\""), "{err}");
+
+        let err = error(
+            r#"
+            [[dashboards]]
+            name = "Ops"
+            rows = [{ widgets = [{ widget = "cpu_gauge", size = "60%" }, { widget = "mem_gauge", size = "50%" }] }]
+            "#,
+        );
+        assert!(err.contains("percentages add up to 110%"), "{err}");
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+
+use crate::sockets;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FdKind {
+    File,
+    Socket,
+    Pipe,
+    Device,
+    Other,
+}
+
+impl FdKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            FdKind::File => "file",
+            FdKind::Socket => "socket",
+            FdKind::Pipe => "pipe",
+            FdKind::Device => "device",
+            FdKind::Other => "other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenFile {
+    pub fd: u32,
+    pub kind: FdKind,
+    /// Path, socket endpoints, or the raw link target for anything else
+    pub target: String,
+}
+
+/// Number of open file descriptors, or `None` when `/proc/<pid>/fd` is not
+/// readable (other users' processes without root, or not Linux).
+pub fn count(pid: u32) -> Option<usize> {
+    Some(fs::read_dir(format!("/proc/{}/fd", pid)).ok()?.count())
+}
+
+/// Lists the open file descriptors of `pid` in fd order, resolving sockets to
+/// their TCP/UDP endpoints where possible.
+pub fn list(pid: u32) -> io::Result<Vec<OpenFile>> {
+    let mut sockets: Option<HashMap<u64, sockets::Socket>> = None;
+    let mut files = Vec::new();
+    for entry in fs::read_dir(format!("/proc/{}/fd", pid))? {
+        let entry = entry?;
+        let Some(fd) = entry.file_name().to_str().and_then(|name| name.parse().ok()) else {
+            continue;
+        };
+        // The fd may have been closed since the directory was read
+        let Ok(link) = fs::read_link(entry.path()) else {
+            continue;
+        };
+        let link = link.to_string_lossy().into_owned();
+
+        let (kind, target) = if let Some(inode) = link.strip_prefix("socket:[").and_then(|rest| rest.strip_suffix(']')) {
+            let sockets = sockets.get_or_insert_with(|| {
+                sockets::read_all().into_iter().map(|socket| (socket.inode, socket)).collect()
+            });
+            let target = match inode.parse().ok().and_then(|inode: u64| sockets.get(&inode)) {
+                Some(socket) => socket.to_string(),
+                None => format!("{} (unix or other)", link),
+            };
+            (FdKind::Socket, target)
+        } else if link.starts_with("pipe:") {
+            (FdKind::Pipe, link)
+        } else if link.starts_with("/dev/") {
+            (FdKind::Device, link)
+        } else if link.starts_with('/') {
+            (FdKind::File, link)
+        } else {
+            (FdKind::Other, link)
+        };
+        files.push(OpenFile { fd, kind, target });
+    }
+    files.sort_by_key(|file| file.fd);
+    Ok(files)
+}
@@ -0,0 +1,114 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use sysinfo::Networks;
+
+use imp::addresses;
+
+/// Link details and error counters of one network interface.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Interface {
+    pub name: String,
+    /// Addresses with their prefix length, e.g. `192.168.1.20/24`
+    pub addresses: Vec<String>,
+    pub mac: String,
+    pub mtu: Option<u32>,
+    /// `operstate` from sysfs: up, down, dormant, unknown, ...
+    pub state: String,
+    /// Link speed in Mb/s; virtual and disconnected links report none
+    pub speed: Option<u32>,
+    /// Loopback, veth, bridges and the like, which have no hardware device
+    pub is_virtual: bool,
+    pub rx_errors: u64,
+    pub tx_errors: u64,
+    pub rx_dropped: u64,
+    pub tx_dropped: u64,
+}
+
+/// Describes every interface sysinfo knows about, sorted by name.
+pub fn collect(networks: &Networks) -> Vec<Interface> {
+    let mut addresses = addresses();
+    let mut interfaces: Vec<Interface> = networks
+        .iter()
+        .map(|(name, data)| {
+            let dir = format!("/sys/class/net/{}", name);
+            let read = |file: &str| fs::read_to_string(format!("{}/{}", dir, file)).ok().map(|s| s.trim().to_string());
+            let counter = |file: &str| read(&format!("statistics/{}", file)).and_then(|s| s.parse().ok()).unwrap_or(0);
+            Interface {
+                name: name.clone(),
+                addresses: addresses.remove(name).unwrap_or_default(),
+                mac: data.mac_address().to_string(),
+                mtu: read("mtu").and_then(|s| s.parse().ok()),
+                state: read("operstate").unwrap_or_else(|| "unknown".to_string()),
+                // -1 when the driver cannot tell, and unreadable while the link is down
+                speed: read("speed").and_then(|s| s.parse::<i64>().ok()).filter(|&speed| speed > 0).map(|speed| speed as u32),
+                is_virtual: fs::canonicalize(&dir).is_ok_and(|path| path.starts_with("/sys/devices/virtual")),
+                rx_errors: counter("rx_errors"),
+                tx_errors: counter("tx_errors"),
+                rx_dropped: counter("rx_dropped"),
+                tx_dropped: counter("tx_dropped"),
+            }
+        })
+        .collect();
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    interfaces
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::collections::HashMap;
+    use std::ffi::CStr;
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    /// IPv4 and IPv6 addresses of every interface, from `getifaddrs`.
+    pub fn addresses() -> HashMap<String, Vec<String>> {
+        let mut addresses: HashMap<String, Vec<String>> = HashMap::new();
+        let mut list: *mut libc::ifaddrs = std::ptr::null_mut();
+        if unsafe { libc::getifaddrs(&mut list) } != 0 {
+            return addresses;
+        }
+        let mut entry = list;
+        while !entry.is_null() {
+            let ifa = unsafe { &*entry };
+            entry = ifa.ifa_next;
+            let Some(address) = (unsafe { ip_address(ifa.ifa_addr) }) else {
+                continue;
+            };
+            let prefix = match unsafe { ip_address(ifa.ifa_netmask) } {
+                Some(IpAddr::V4(mask)) => u32::from(mask).count_ones(),
+                Some(IpAddr::V6(mask)) => u128::from(mask).count_ones(),
+                None => if address.is_ipv4() { 32 } else { 128 },
+            };
+            let name = unsafe { CStr::from_ptr(ifa.ifa_name) }.to_string_lossy().into_owned();
+            addresses.entry(name).or_default().push(format!("{}/{}", address, prefix));
+        }
+        unsafe { libc::freeifaddrs(list) };
+        addresses
+    }
+
+    /// Reads an IPv4 or IPv6 address out of a `sockaddr`; other families give `None`.
+    unsafe fn ip_address(address: *const libc::sockaddr) -> Option<IpAddr> {
+        if address.is_null() {
+            return None;
+        }
+        match (*address).sa_family as libc::c_int {
+            libc::AF_INET => {
+                let address = &*(address as *const libc::sockaddr_in);
+                Some(IpAddr::V4(Ipv4Addr::from(u32::from_be(address.sin_addr.s_addr))))
+            }
+            libc::AF_INET6 => {
+                let address = &*(address as *const libc::sockaddr_in6);
+                Some(IpAddr::V6(Ipv6Addr::from(address.sin6_addr.s6_addr)))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use std::collections::HashMap;
+
+    pub fn addresses() -> HashMap<String, Vec<String>> {
+        HashMap::new()
+    }
+}
@@ -0,0 +1,253 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use regex::Regex;
+use serde::Deserialize;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+
+use imp::file_id;
+
+/// Lines kept in memory across all files; older ones are dropped first.
+const MAX_LINES: usize = 2000;
+/// How much of each file is shown when it is first opened.
+const INITIAL_BYTES: u64 = 64 * 1024;
+/// Upper bound on what is read from one file per refresh, so a burst cannot stall the UI.
+const MAX_READ: u64 = 1024 * 1024;
+
+const DEFAULT_ERROR_PATTERN: &str = r"(?i)\b(error|err|fatal|panic|crit|critical|emerg|alert|failed|failure)\b";
+const DEFAULT_WARNING_PATTERN: &str = r"(?i)\b(warn|warning)\b";
+
+/// The `[logs]` section of the config file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogsConfig {
+    /// Files to tail, e.g. `/var/log/syslog` or a `journalctl -f` saved to disk
+    pub files: Vec<PathBuf>,
+    /// Lines matching this are errors; a built-in pattern is used when unset
+    pub error_pattern: Option<String>,
+    /// Lines matching this (and not the error pattern) are warnings
+    pub warning_pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// The next minimum severity for the severity filter, wrapping around.
+    pub fn cycle(self) -> Self {
+        match self {
+            Severity::Info => Severity::Warning,
+            Severity::Warning => Severity::Error,
+            Severity::Error => Severity::Info,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    /// Increases by one per line across all files
+    pub seq: u64,
+    /// When the line was read, at most one refresh after it was written
+    pub time: DateTime<Local>,
+    pub source: String,
+    pub text: String,
+    pub severity: Severity,
+}
+
+#[derive(Debug)]
+pub struct LogFile {
+    pub path: PathBuf,
+    offset: u64,
+    inode: Option<u64>,
+    partial: String,
+    /// Why the file could not be read on the last refresh
+    pub error: Option<String>,
+}
+
+impl LogFile {
+    fn new(path: PathBuf) -> Self {
+        Self { path, offset: 0, inode: None, partial: String::new(), error: None }
+    }
+
+    /// Reads whatever was appended since the last call. A replaced file (new
+    /// inode, where there are inodes) or a truncated one is read again from the start.
+    fn read_new(&mut self) -> Result<Vec<String>> {
+        let mut file = File::open(&self.path)?;
+        let metadata = file.metadata()?;
+        let first_open = self.inode.is_none();
+        if self.inode != Some(file_id(&metadata)) || metadata.len() < self.offset {
+            self.offset = if first_open { metadata.len().saturating_sub(INITIAL_BYTES) } else { 0 };
+            self.partial.clear();
+            self.inode = Some(file_id(&metadata));
+        }
+        if metadata.len() == self.offset {
+            return Ok(Vec::new());
+        }
+
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buffer = Vec::new();
+        file.take(MAX_READ).read_to_end(&mut buffer)?;
+        // Starting part-way into the file, the first line is most likely cut off
+        let skip_first = first_open && self.offset > 0;
+        self.offset += buffer.len() as u64;
+
+        self.partial.push_str(&String::from_utf8_lossy(&buffer));
+        let Some(end) = self.partial.rfind('\n') else {
+            return Ok(Vec::new());
+        };
+        let complete: String = self.partial.drain(..=end).collect();
+        Ok(complete
+            .lines()
+            .skip(usize::from(skip_first))
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+pub struct LogMonitor {
+    pub files: Vec<LogFile>,
+    pub lines: VecDeque<LogLine>,
+    /// Error lines read per refresh, sampled alongside the CPU and memory history
+    pub error_history: Vec<u64>,
+    error_pattern: Regex,
+    warning_pattern: Regex,
+    next_seq: u64,
+}
+
+impl LogMonitor {
+    pub fn new(config: &LogsConfig) -> Result<Self> {
+        let compile = |pattern: &Option<String>, default: &str, kind: &str| {
+            Regex::new(pattern.as_deref().unwrap_or(default)).with_context(|| format!("invalid {}_pattern", kind))
+        };
+        Ok(Self {
+            files: config.files.iter().cloned().map(LogFile::new).collect(),
+            lines: VecDeque::new(),
+            error_history: Vec::new(),
+            error_pattern: compile(&config.error_pattern, DEFAULT_ERROR_PATTERN, "error")?,
+            warning_pattern: compile(&config.warning_pattern, DEFAULT_WARNING_PATTERN, "warning")?,
+            next_seq: 0,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn latest_seq(&self) -> Option<u64> {
+        self.lines.back().map(|line| line.seq)
+    }
+
+    /// Reads new lines from every file and records how many were errors. The
+    /// backlog shown when a file is first opened does not count towards the rate.
+    pub fn update(&mut self) {
+        if self.files.is_empty() {
+            return;
+        }
+        let now = Local::now();
+        let mut errors = 0;
+        for index in 0..self.files.len() {
+            let backlog = self.files[index].inode.is_none();
+            let new_lines = match self.files[index].read_new() {
+                Ok(lines) => {
+                    self.files[index].error = None;
+                    lines
+                }
+                Err(err) => {
+                    self.files[index].error = Some(err.to_string());
+                    continue;
+                }
+            };
+            let source = self.files[index]
+                .path
+                .file_name()
+                .map_or_else(|| self.files[index].path.display().to_string(), |name| name.to_string_lossy().into_owned());
+            for text in new_lines {
+                let severity = self.classify(&text);
+                if severity == Severity::Error && !backlog {
+                    errors += 1;
+                }
+                self.lines.push_back(LogLine { seq: self.next_seq, time: now, source: source.clone(), text, severity });
+                self.next_seq += 1;
+            }
+        }
+        while self.lines.len() > MAX_LINES {
+            self.lines.pop_front();
+        }
+
+        self.error_history.push(errors);
+        if self.error_history.len() > 60 {
+            self.error_history.remove(0);
+        }
+    }
+
+    fn classify(&self, text: &str) -> Severity {
+        if self.error_pattern.is_match(text) {
+            Severity::Error
+        } else if self.warning_pattern.is_match(text) {
+            Severity::Warning
+        } else {
+            Severity::Info
+        }
+    }
+
+    /// Lines that pass the view's pause point, severity and text filters, oldest first.
+    pub fn visible<'a>(&'a self, view: &'a LogView) -> impl Iterator<Item = &'a LogLine> + 'a {
+        let filter = view.filter().to_lowercase();
+        self.lines.iter().filter(move |line| {
+            view.paused_at.is_none_or(|seq| line.seq <= seq)
+                && line.severity >= view.min_severity
+                && (filter.is_empty() || line.text.to_lowercase().contains(&filter))
+        })
+    }
+}
+
+/// How the Logs tab is being viewed.
+#[derive(Debug, Default)]
+pub struct LogView {
+    /// Lines scrolled back from the newest visible line
+    pub scroll: usize,
+    /// Newest line shown while paused; later lines are still collected
+    pub paused_at: Option<u64>,
+    pub min_severity: Severity,
+    pub filter: String,
+    /// Filter text being typed, applied as it changes
+    pub editing: Option<String>,
+    pub height: Cell<usize>, // lines visible in the last frame
+}
+
+impl LogView {
+    pub fn filter(&self) -> &str {
+        self.editing.as_deref().unwrap_or(&self.filter)
+    }
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::fs::Metadata;
+    use std::os::unix::fs::MetadataExt;
+
+    /// Identifies the file behind a path, so rotation to a new file is noticed.
+    pub fn file_id(metadata: &Metadata) -> u64 {
+        metadata.ino()
+    }
+}
+
+#[cfg(not(unix))]
+mod imp {
+    use std::fs::Metadata;
+
+    /// Without inodes every file looks the same, and rotation is only noticed
+    /// once the new file is smaller than what was already read.
+    pub fn file_id(_metadata: &Metadata) -> u64 {
+        0
+    }
+}
@@ -1,7 +1,10 @@
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -11,14 +14,45 @@ use ratatui::{
 };
 use std::{
     io,
+    net::SocketAddr,
+    path::PathBuf,
     time::{Duration, Instant},
 };
 
+mod alerts;
 mod app;
+mod bandwidth;
+mod cgroup;
+mod config;
+mod connections;
+mod dashboard;
+mod fds;
+mod interfaces;
+mod logs;
+mod meminfo;
+mod plugins;
+mod power;
+mod pressure;
+mod process_control;
+mod process_events;
+mod process_status;
+mod procstat;
+mod remote;
+mod sensors;
+mod services;
+mod sockets;
 mod system_info;
+mod transport;
 mod ui;
+mod watch;
 
 use app::{App, AppResult};
+use cgroup::Perspective;
+use config::Config;
+use transport::Connector;
+use ui::HitAreas;
+use logs::LogMonitor;
+use watch::WatchList;
 
 #[derive(Parser)]
 #[command(name = "system_monitor")]
@@ -27,31 +61,84 @@ struct Cli {
     /// Update interval in milliseconds
     #[arg(short, long, default_value_t = 1000)]
     interval: u64,
+
+    /// Measure gauges against host totals or the container's cgroup limits
+    #[arg(short, long, value_enum, default_value_t = Perspective::Auto)]
+    perspective: Perspective,
+
+    /// Monitor a remote agent instead of this machine (repeat for multiple hosts)
+    #[arg(long = "connect", value_name = "HOST:PORT")]
+    connect: Vec<String>,
+
+    /// Config file (default: ~/.config/rust-system-monitor/config.toml)
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Leave the mouse to the terminal, e.g. for selecting text
+    #[arg(long)]
+    no_mouse: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Serve snapshots of this machine to remote monitors
+    Agent {
+        /// Address to accept monitor connections on
+        #[arg(long, default_value = "127.0.0.1:7878")]
+        listen: SocketAddr,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    let config = Config::load(cli.config.as_deref())?;
+    let tick_rate = Duration::from_millis(cli.interval);
+
+    let mut app = App::new();
+    app.perspective = cli.perspective;
+    alerts::validate(&config.alerts).context("invalid [[alerts]] rule in config file")?;
+    app.alert_rules = config.alerts.clone();
+    app.watch = WatchList::new(config.watch.clone());
+    app.config_path = Config::path(cli.config.as_deref());
+    app.dashboards = dashboard::build(&config.dashboards, ui::TABS).context("invalid dashboard layout in config file")?;
+    app.plugins = plugins::start(&config.plugins).context("invalid plugin in config file")?;
+    app.logs = LogMonitor::new(&config.logs).context("invalid [logs] section in config file")?;
+
+    if let Some(Command::Agent { listen }) = cli.command {
+        return remote::run_agent(listen, app, tick_rate, config.agent).await;
+    }
+    if !cli.connect.is_empty() {
+        let connector = Connector::new(config.client.tls.as_ref())?;
+        app.hosts = cli
+            .connect
+            .into_iter()
+            .map(|address| remote::spawn_client(address, connector.clone(), config.client.token.clone()))
+            .collect();
+    }
     
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    if !cli.no_mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app and run it
-    let tick_rate = Duration::from_millis(cli.interval);
-    let app = App::new();
+    // Run the app
     let res = run_app(&mut terminal, app, tick_rate).await;
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    if !cli.no_mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
     terminal.show_cursor()?;
 
     if let Err(err) = res {
@@ -67,26 +154,72 @@ async fn run_app<B: Backend>(
     tick_rate: Duration,
 ) -> AppResult<()> {
     let mut last_tick = Instant::now();
+    let mut hits = HitAreas::default();
 
     loop {
-        terminal.draw(|f| ui::render(f, &app))?;
+        terminal.draw(|f| hits = ui::render(f, &app))?;
 
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
 
         if crossterm::event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
+            let event = event::read()?;
+            if let Event::Mouse(mouse) = event {
+                handle_mouse(&mut app, &hits, mouse);
+            }
+            if let Event::Key(key) = event {
+                if key.kind == KeyEventKind::Press && app.is_editing_filter() {
+                    match key.code {
+                        KeyCode::Esc => app.cancel_filter(),
+                        KeyCode::Enter => app.apply_filter(),
+                        KeyCode::Backspace => app.pop_filter(),
+                        KeyCode::Char(c) => app.push_filter(c),
+                        _ => {}
+                    }
+                } else if key.kind == KeyEventKind::Press && app.dialog.is_some() {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => app.dialog_cancel(),
+                        KeyCode::Enter | KeyCode::Char('y') => app.dialog_submit(),
+                        KeyCode::Char('k') | KeyCode::Up | KeyCode::Char('+') => app.dialog_up(),
+                        KeyCode::Char('j') | KeyCode::Down | KeyCode::Char('-') => app.dialog_down(),
+                        KeyCode::Char('h') | KeyCode::Left => app.dialog_left(),
+                        KeyCode::Char('l') | KeyCode::Right => app.dialog_right(),
+                        KeyCode::Char(' ') => app.dialog_toggle(),
+                        _ => {}
+                    }
+                } else if key.kind == KeyEventKind::Press {
                     match key.code {
                         KeyCode::Char('q') => return Ok(()),
+                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => app.half_page_down(),
+                        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => app.half_page_up(),
+                        KeyCode::PageDown => app.page_down(),
+                        KeyCode::PageUp => app.page_up(),
+                        KeyCode::Home | KeyCode::Char('g') => app.first_row(),
+                        KeyCode::End | KeyCode::Char('G') => app.last_row(),
                         KeyCode::Char('h') | KeyCode::Left => app.previous_tab(),
                         KeyCode::Char('l') | KeyCode::Right => app.next_tab(),
-                        KeyCode::Char('j') | KeyCode::Down => app.next_process(),
-                        KeyCode::Char('k') | KeyCode::Up => app.previous_process(),
+                        KeyCode::Char('j') | KeyCode::Down => app.next_row(),
+                        KeyCode::Char('k') | KeyCode::Up => app.previous_row(),
                         KeyCode::Char('r') => app.refresh(),
                         KeyCode::Char('s') => app.toggle_sort(),
+                        KeyCode::Char('I') => app.toggle_cpu_mode(),
+                        KeyCode::Char('u') => app.jump_to_unit_or_process(),
+                        KeyCode::Enter => app.open_selected_host(),
                         KeyCode::Delete => app.kill_selected_process(),
+                        KeyCode::Char('n') => app.open_renice_dialog(),
+                        KeyCode::Char('o') => app.open_ionice_dialog(),
+                        KeyCode::Char('a') => app.open_affinity_dialog(),
+                        KeyCode::Char('z') => app.stop_selected_process(),
+                        KeyCode::Char('c') => app.continue_selected_process(),
+                        KeyCode::Char('w') => app.toggle_watch_selected(),
+                        KeyCode::Char('f') => app.open_files_dialog(),
+                        KeyCode::Char('p') => app.toggle_log_pause(),
+                        KeyCode::Char('e') => app.cycle_log_severity(),
+                        KeyCode::Char('/') => app.start_filter(),
+                        KeyCode::Char('t') => app.cycle_connection_state(),
+                        KeyCode::Char('v') => app.toggle_virtual_interfaces(),
+                        KeyCode::Char('x') => app.toggle_problem_filter(),
                         _ => {}
                     }
                 }
@@ -98,4 +231,22 @@ async fn run_app<B: Backend>(
             last_tick = Instant::now();
         }
     }
+}
+
+fn handle_mouse(app: &mut App, hits: &HitAreas, mouse: MouseEvent) {
+    let (column, row) = (mouse.column, mouse.row);
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(tab) = hits.tab_at(column, row) {
+                app.select_tab(tab);
+            } else if let Some(sort_by) = hits.sort_column_at(column, row) {
+                app.sort_by_column(sort_by);
+            } else if let Some(index) = hits.row_at(column, row) {
+                app.select_row(index);
+            }
+        }
+        MouseEventKind::ScrollDown if hits.row_at(column, row).is_some() => app.move_row(1),
+        MouseEventKind::ScrollUp if hits.row_at(column, row).is_some() => app.move_row(-1),
+        _ => {}
+    }
 }
\ No newline at end of file
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+
+/// Memory accounting from `/proc/meminfo`, in bytes.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MemoryBreakdown {
+    pub total: u64,
+    pub free: u64,
+    pub available: u64,
+    pub buffers: u64,
+    pub cached: u64, // page cache plus reclaimable slab, as free(1) reports it
+    pub shared: u64,
+    pub dirty: u64,
+    pub writeback: u64,
+    pub huge_pages_total: u64,
+    pub huge_pages_free: u64,
+    pub huge_page_size: u64,
+    pub swap_total: u64,
+    pub swap_free: u64,
+    pub swap_cached: u64,
+}
+
+impl MemoryBreakdown {
+    /// Memory not accounted for by free pages, buffers or cache, so that
+    /// `used + buffers + cached + free == total`.
+    pub fn used(&self) -> u64 {
+        self.total
+            .saturating_sub(self.free)
+            .saturating_sub(self.buffers)
+            .saturating_sub(self.cached)
+    }
+}
+
+pub fn read() -> Option<MemoryBreakdown> {
+    fs::read_to_string("/proc/meminfo").ok().map(|contents| parse_meminfo(&contents))
+}
+
+/// Parses `/proc/meminfo`. Missing fields are reported as zero.
+pub fn parse_meminfo(contents: &str) -> MemoryBreakdown {
+    let fields: HashMap<&str, u64> = contents
+        .lines()
+        .filter_map(|line| {
+            let (key, rest) = line.split_once(':')?;
+            let mut parts = rest.split_whitespace();
+            let value: u64 = parts.next()?.parse().ok()?;
+            // Most fields are in kB; the HugePages_* counts have no unit
+            let scale = if parts.next() == Some("kB") { 1024 } else { 1 };
+            Some((key, value * scale))
+        })
+        .collect();
+    let field = |key: &str| fields.get(key).copied().unwrap_or(0);
+
+    MemoryBreakdown {
+        total: field("MemTotal"),
+        free: field("MemFree"),
+        available: field("MemAvailable"),
+        buffers: field("Buffers"),
+        cached: field("Cached") + field("SReclaimable"),
+        shared: field("Shmem"),
+        dirty: field("Dirty"),
+        writeback: field("Writeback"),
+        huge_pages_total: field("HugePages_Total"),
+        huge_pages_free: field("HugePages_Free"),
+        huge_page_size: field("Hugepagesize"),
+        swap_total: field("SwapTotal"),
+        swap_free: field("SwapFree"),
+        swap_cached: field("SwapCached"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMINFO: &str = "\
+MemTotal:        8000000 kB
+MemFree:         1000000 kB
+MemAvailable:    5000000 kB
+Buffers:          200000 kB
+Cached:          3000000 kB
+SwapCached:         1000 kB
+Dirty:               500 kB
+Writeback:             0 kB
+Shmem:            100000 kB
+SReclaimable:     300000 kB
+SwapTotal:       2000000 kB
+SwapFree:        1500000 kB
+HugePages_Total:       4
+HugePages_Free:        2
+Hugepagesize:       2048 kB
+";
+
+    #[test]
+    fn parses_fields_in_bytes() {
+        let memory = parse_meminfo(MEMINFO);
+        assert_eq!(memory.total, 8000000 * 1024);
+        assert_eq!(memory.available, 5000000 * 1024);
+        // Reclaimable slab counts as cache
+        assert_eq!(memory.cached, 3300000 * 1024);
+        assert_eq!(memory.swap_total, 2000000 * 1024);
+        assert_eq!(memory.swap_free, 1500000 * 1024);
+        assert_eq!((memory.huge_pages_total, memory.huge_pages_free), (4, 2));
+        assert_eq!(memory.huge_page_size, 2048 * 1024);
+        assert_eq!(memory.used() + memory.buffers + memory.cached + memory.free, memory.total);
+    }
+
+    #[test]
+    fn missing_fields_are_zero() {
+        // Kernels before 3.14 have no MemAvailable
+        let memory = parse_meminfo("MemTotal: 1024 kB\nMemFree: 512 kB\n");
+        assert_eq!(memory.available, 0);
+        assert_eq!(memory.swap_total, 0);
+        assert_eq!(memory.used(), 512 * 1024);
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::process::Command;
+
+/// An external program polled for metrics, configured as `[[plugins]]`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginConfig {
+    pub name: String,
+    /// Program and arguments, run without a shell
+    pub command: Vec<String>,
+    /// Seconds between runs; a run taking longer than this is killed
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+}
+
+fn default_interval() -> u64 {
+    10
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Display {
+    Gauge,
+    Sparkline,
+    #[default]
+    Table,
+}
+
+/// One line of plugin output, e.g.
+/// `{"name": "build_queue", "value": 12, "unit": "jobs", "display": "gauge", "max": 50}`.
+#[derive(Debug, Deserialize)]
+struct MetricLine {
+    name: String,
+    value: f64,
+    unit: Option<String>,
+    #[serde(default)]
+    display: Display,
+    max: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomMetric {
+    pub name: String,
+    pub value: f64,
+    pub unit: Option<String>,
+    pub display: Display,
+    /// Full scale for gauges; 100 when not given
+    pub max: Option<f64>,
+    pub history: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginState {
+    pub name: String,
+    pub metrics: Vec<CustomMetric>,
+    /// Why the last run failed; metrics from earlier runs are kept
+    pub error: Option<String>,
+    /// Unix time of the last successful run
+    pub last_run: Option<u64>,
+}
+
+pub type Plugin = Arc<Mutex<PluginState>>;
+
+/// Checks every configured plugin and starts a background task for each.
+pub fn start(configs: &[PluginConfig]) -> Result<Vec<Plugin>> {
+    for (i, config) in configs.iter().enumerate() {
+        if config.name.trim().is_empty() {
+            bail!("plugin {}: name must not be empty", i + 1);
+        }
+        if config.name.contains('.') {
+            bail!("plugin \"{}\": names must not contain '.', which separates alert metric names", config.name);
+        }
+        if configs[..i].iter().any(|other| other.name == config.name) {
+            bail!("plugin \"{}\" is defined more than once", config.name);
+        }
+        if config.command.first().is_none_or(|program| program.is_empty()) {
+            bail!("plugin \"{}\": command must name a program", config.name);
+        }
+        if config.interval == 0 {
+            bail!("plugin \"{}\": interval must be at least 1 second", config.name);
+        }
+    }
+    Ok(configs.iter().map(spawn).collect())
+}
+
+fn spawn(config: &PluginConfig) -> Plugin {
+    let plugin = Arc::new(Mutex::new(PluginState {
+        name: config.name.clone(),
+        metrics: Vec::new(),
+        error: None,
+        last_run: None,
+    }));
+
+    let state = plugin.clone();
+    let command = config.command.clone();
+    let interval = Duration::from_secs(config.interval);
+    tokio::spawn(async move {
+        loop {
+            let result = run(&command, interval).await;
+            {
+                let mut state = state.lock().unwrap();
+                match result {
+                    Ok(lines) => {
+                        state.metrics = merge(&state.metrics, lines);
+                        state.error = None;
+                        state.last_run = SystemTime::now().duration_since(UNIX_EPOCH).ok().map(|d| d.as_secs());
+                    }
+                    Err(err) => state.error = Some(err.to_string()),
+                }
+            }
+            tokio::time::sleep(interval).await;
+        }
+    });
+
+    plugin
+}
+
+async fn run(command: &[String], timeout: Duration) -> Result<Vec<MetricLine>> {
+    let child = Command::new(&command[0]).args(&command[1..]).kill_on_drop(true).output();
+    let output = match tokio::time::timeout(timeout, child).await {
+        Ok(output) => output?,
+        Err(_) => bail!("timed out after {}s", timeout.as_secs()),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        match stderr.lines().next() {
+            Some(line) => bail!("{}: {}", output.status, line),
+            None => bail!("{}", output.status),
+        }
+    }
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| match serde_json::from_str(line) {
+            Ok(metric) => Ok(metric),
+            Err(err) => bail!("line {}: {}", i + 1, err),
+        })
+        .collect()
+}
+
+/// Builds the new metric list, carrying over the history of metrics seen before.
+/// Metrics missing from the latest output are dropped.
+fn merge(previous: &[CustomMetric], lines: Vec<MetricLine>) -> Vec<CustomMetric> {
+    lines
+        .into_iter()
+        .map(|line| {
+            let mut history = previous
+                .iter()
+                .find(|metric| metric.name == line.name)
+                .map(|metric| metric.history.clone())
+                .unwrap_or_default();
+            history.push(line.value);
+            if history.len() > 60 {
+                history.remove(0);
+            }
+            CustomMetric {
+                name: line.name,
+                value: line.value,
+                unit: line.unit,
+                display: line.display,
+                max: line.max,
+                history,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+pub const DEFAULT_DIR: &str = "/sys/class/power_supply";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Battery {
+    pub name: String,
+    /// Charge level, 0-100
+    pub percent: f32,
+    /// Charging, Discharging, Full, Not charging or Unknown
+    pub status: String,
+    /// Watts flowing in or out, when the battery reports it
+    pub rate: Option<f32>,
+    /// Seconds until empty while discharging, or until full while charging
+    pub time_remaining: Option<u64>,
+    /// Full charge capacity as a percentage of the design capacity
+    pub health: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerStatus {
+    pub batteries: Vec<Battery>,
+    /// Whether any mains or USB supply is connected; `None` when none is listed
+    pub ac_online: Option<bool>,
+}
+
+impl PowerStatus {
+    /// Mean charge level across batteries.
+    pub fn percent(&self) -> f32 {
+        self.batteries.iter().map(|battery| battery.percent).sum::<f32>() / self.batteries.len() as f32
+    }
+}
+
+/// Reads every supply under `dir`, laid out like `/sys/class/power_supply`.
+/// Returns `None` when there is no system battery, so the panel can be hidden.
+pub fn read(dir: &Path) -> Option<PowerStatus> {
+    let mut batteries = Vec::new();
+    let mut ac_online = None;
+    let mut entries: Vec<_> = fs::read_dir(dir).ok()?.flatten().map(|entry| entry.path()).collect();
+    entries.sort();
+    for path in entries {
+        let read = |file: &str| fs::read_to_string(path.join(file)).ok().map(|s| s.trim().to_string());
+        match read("type").as_deref() {
+            // Mice and headsets report a "Device" scope; only the system's own batteries count
+            Some("Battery") if read("scope").as_deref() != Some("Device") => {
+                let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+                if let Some(battery) = read_battery(name, &read) {
+                    batteries.push(battery);
+                }
+            }
+            Some("Mains") | Some("USB") => {
+                let online = read("online").as_deref() == Some("1");
+                ac_online = Some(ac_online.unwrap_or(false) || online);
+            }
+            _ => {}
+        }
+    }
+    (!batteries.is_empty()).then_some(PowerStatus { batteries, ac_online })
+}
+
+/// Batteries report either energy (µWh, with power in µW) or charge (µAh, with
+/// current in µA); both give hours when divided by the matching rate.
+fn read_battery(name: String, read: &dyn Fn(&str) -> Option<String>) -> Option<Battery> {
+    let number = |file: &str| read(file).and_then(|s| s.parse::<f64>().ok());
+    let (now, full, design, flow) = match number("energy_now") {
+        Some(now) => (Some(now), number("energy_full"), number("energy_full_design"), number("power_now")),
+        None => (number("charge_now"), number("charge_full"), number("charge_full_design"), number("current_now")),
+    };
+    // Some drivers report a negative current while discharging
+    let flow = flow.map(f64::abs).filter(|&flow| flow > 0.0);
+    let status = read("status").unwrap_or_else(|| "Unknown".to_string());
+
+    let percent = match (number("capacity"), now, full) {
+        (Some(capacity), _, _) => capacity,
+        (None, Some(now), Some(full)) if full > 0.0 => now / full * 100.0,
+        _ => return None,
+    };
+    let rate = match number("power_now") {
+        Some(power) => Some(power.abs() / 1e6),
+        None => number("current_now").zip(number("voltage_now")).map(|(current, voltage)| (current * voltage).abs() / 1e12),
+    };
+    let hours = match status.as_str() {
+        "Discharging" => now.zip(flow).map(|(now, flow)| now / flow),
+        "Charging" => now.zip(full).zip(flow).map(|((now, full), flow)| (full - now).max(0.0) / flow),
+        _ => None,
+    };
+    Some(Battery {
+        name,
+        percent: percent.clamp(0.0, 100.0) as f32,
+        status,
+        rate: rate.filter(|&rate| rate > 0.0).map(|rate| rate as f32),
+        time_remaining: hours.map(|hours| (hours * 3600.0) as u64),
+        health: full.zip(design).filter(|&(_, design)| design > 0.0).map(|(full, design)| (full / design * 100.0) as f32),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates `dir/<name>` with one file per `(file, contents)` pair.
+    fn supply(dir: &Path, name: &str, files: &[(&str, &str)]) {
+        let path = dir.join(name);
+        fs::create_dir_all(&path).unwrap();
+        for (file, contents) in files {
+            fs::write(path.join(file), format!("{}\n", contents)).unwrap();
+        }
+    }
+
+    #[test]
+    fn reads_energy_battery() {
+        let dir = tempfile::tempdir().unwrap();
+        supply(dir.path(), "AC", &[("type", "Mains"), ("online", "0")]);
+        supply(
+            dir.path(),
+            "BAT0",
+            &[
+                ("type", "Battery"),
+                ("scope", "System"),
+                ("status", "Discharging"),
+                ("energy_now", "25000000"),
+                ("energy_full", "50000000"),
+                ("energy_full_design", "62500000"),
+                ("power_now", "12500000"),
+            ],
+        );
+
+        let status = read(dir.path()).unwrap();
+        assert_eq!(status.ac_online, Some(false));
+        let battery = &status.batteries[0];
+        assert_eq!(battery.name, "BAT0");
+        assert_eq!(battery.percent, 50.0);
+        assert_eq!(battery.rate, Some(12.5));
+        assert_eq!(battery.time_remaining, Some(2 * 3600));
+        assert_eq!(battery.health, Some(80.0));
+    }
+
+    #[test]
+    fn reads_charge_battery() {
+        let dir = tempfile::tempdir().unwrap();
+        supply(dir.path(), "USB", &[("type", "USB"), ("online", "1")]);
+        supply(
+            dir.path(),
+            "BAT1",
+            &[
+                ("type", "Battery"),
+                ("status", "Charging"),
+                ("capacity", "75"),
+                ("charge_now", "3000000"),
+                ("charge_full", "4000000"),
+                ("current_now", "-2000000"),
+                ("voltage_now", "12000000"),
+            ],
+        );
+
+        let status = read(dir.path()).unwrap();
+        assert_eq!(status.ac_online, Some(true));
+        let battery = &status.batteries[0];
+        assert_eq!(battery.percent, 75.0);
+        assert_eq!(battery.rate, Some(24.0));
+        assert_eq!(battery.time_remaining, Some(1800));
+        assert_eq!(battery.health, None);
+    }
+
+    #[test]
+    fn ignores_device_batteries() {
+        let dir = tempfile::tempdir().unwrap();
+        supply(dir.path(), "hidpp_battery_0", &[("type", "Battery"), ("scope", "Device"), ("capacity", "40")]);
+        assert!(read(dir.path()).is_none());
+    }
+
+    #[test]
+    fn no_battery_gives_none() {
+        let dir = tempfile::tempdir().unwrap();
+        supply(dir.path(), "AC", &[("type", "Mains"), ("online", "1")]);
+        assert!(read(dir.path()).is_none());
+        assert!(read(&dir.path().join("missing")).is_none());
+    }
+}
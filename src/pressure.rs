@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Share of wall time that tasks were stalled, averaged over 10s, 60s and 300s.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PressureAverages {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+}
+
+/// One pressure file: `some` tasks stalled, and optionally `full` (all tasks stalled).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Pressure {
+    pub some: PressureAverages,
+    pub full: Option<PressureAverages>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PressureStats {
+    pub cpu: Option<Pressure>,
+    pub memory: Option<Pressure>,
+    pub io: Option<Pressure>,
+}
+
+impl PressureStats {
+    pub fn get(&self, resource: &str) -> Option<&Pressure> {
+        match resource {
+            "cpu" => self.cpu.as_ref(),
+            "memory" => self.memory.as_ref(),
+            "io" => self.io.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Reads system-wide pressure, or `None` when the kernel lacks PSI.
+pub fn read_system() -> Option<PressureStats> {
+    read_files(
+        &Path::new("/proc/pressure").join("cpu"),
+        &Path::new("/proc/pressure").join("memory"),
+        &Path::new("/proc/pressure").join("io"),
+    )
+}
+
+/// Reads the pressure of a single cgroup v2 directory.
+pub fn read_cgroup(dir: &Path) -> Option<PressureStats> {
+    read_files(&dir.join("cpu.pressure"), &dir.join("memory.pressure"), &dir.join("io.pressure"))
+}
+
+fn read_files(cpu: &Path, memory: &Path, io: &Path) -> Option<PressureStats> {
+    let read = |path: &Path| fs::read_to_string(path).ok().and_then(|contents| parse_pressure(&contents));
+    let stats = PressureStats {
+        cpu: read(cpu),
+        memory: read(memory),
+        io: read(io),
+    };
+    (stats != PressureStats::default()).then_some(stats)
+}
+
+/// Parses a PSI file such as `/proc/pressure/memory`.
+pub fn parse_pressure(contents: &str) -> Option<Pressure> {
+    let mut some = None;
+    let mut full = None;
+    for line in contents.lines() {
+        let mut parts = line.split_whitespace();
+        let kind = parts.next();
+        let mut averages = PressureAverages::default();
+        for part in parts {
+            match part.split_once('=') {
+                Some(("avg10", value)) => averages.avg10 = value.parse().ok()?,
+                Some(("avg60", value)) => averages.avg60 = value.parse().ok()?,
+                Some(("avg300", value)) => averages.avg300 = value.parse().ok()?,
+                _ => {}
+            }
+        }
+        match kind {
+            Some("some") => some = Some(averages),
+            Some("full") => full = Some(averages),
+            _ => {}
+        }
+    }
+    Some(Pressure { some: some?, full })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_some_and_full() {
+        let contents = "\
+some avg10=1.53 avg60=0.87 avg300=0.28 total=123456
+full avg10=0.25 avg60=0.10 avg300=0.03 total=45678
+";
+        let pressure = parse_pressure(contents).unwrap();
+        assert_eq!(pressure.some, PressureAverages { avg10: 1.53, avg60: 0.87, avg300: 0.28 });
+        assert_eq!(pressure.full.map(|full| full.avg10), Some(0.25));
+    }
+
+    #[test]
+    fn cpu_pressure_may_lack_full() {
+        // System-wide CPU pressure had no `full` line before Linux 5.13
+        let pressure = parse_pressure("some avg10=4.00 avg60=2.00 avg300=1.00 total=99\n").unwrap();
+        assert_eq!(pressure.some.avg10, 4.0);
+        assert_eq!(pressure.full, None);
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        assert_eq!(parse_pressure(""), None);
+        assert_eq!(parse_pressure("full avg10=0.00 avg60=0.00 avg300=0.00 total=0\n"), None);
+        assert_eq!(parse_pressure("some avg10=abc avg60=0.00 avg300=0.00 total=0\n"), None);
+    }
+}
@@ -0,0 +1,241 @@
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, Signal, System};
+
+/// I/O scheduling class, as set by `ionice -c`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IoClass {
+    None,
+    RealTime,
+    BestEffort,
+    Idle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IoPriority {
+    pub class: IoClass,
+    pub level: u8, // 0 (highest) to 7, unused for Idle
+}
+
+impl IoPriority {
+    pub fn label(&self) -> String {
+        match self.class {
+            IoClass::None => "none".to_string(),
+            IoClass::RealTime => format!("rt/{}", self.level),
+            IoClass::BestEffort => format!("be/{}", self.level),
+            IoClass::Idle => "idle".to_string(),
+        }
+    }
+}
+
+/// An action on a process, performed locally or sent to a remote agent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ProcessAction {
+    Kill { pid: u32 },
+    Stop { pid: u32 },
+    Continue { pid: u32 },
+    Renice { pid: u32, nice: i32 },
+    Ionice { pid: u32, priority: IoPriority },
+    Affinity { pid: u32, cores: Vec<usize> },
+}
+
+impl ProcessAction {
+    pub fn describe(&self) -> String {
+        match self {
+            ProcessAction::Kill { pid } => format!("kill pid {pid}"),
+            ProcessAction::Stop { pid } => format!("stop pid {pid}"),
+            ProcessAction::Continue { pid } => format!("continue pid {pid}"),
+            ProcessAction::Renice { pid, nice } => format!("set nice of pid {pid} to {nice}"),
+            ProcessAction::Ionice { pid, priority } => format!("set io priority of pid {pid} to {}", priority.label()),
+            ProcessAction::Affinity { pid, cores } => {
+                format!("set affinity of pid {pid} to cpus {}", format_cpu_list(cores))
+            }
+        }
+    }
+}
+
+/// Performs `action` on this machine, describing the outcome either way.
+pub fn execute(action: &ProcessAction) -> Result<String, String> {
+    match action {
+        ProcessAction::Kill { pid } => signal(*pid, Signal::Kill),
+        ProcessAction::Stop { pid } => signal(*pid, Signal::Stop),
+        ProcessAction::Continue { pid } => signal(*pid, Signal::Continue),
+        ProcessAction::Renice { pid, nice } => imp::set_nice(*pid, *nice).map(|_| "done".to_string()),
+        ProcessAction::Ionice { pid, priority } => imp::set_io_priority(*pid, *priority).map(|_| "done".to_string()),
+        ProcessAction::Affinity { pid, cores } => {
+            if cores.is_empty() {
+                return Err("at least one cpu must be selected".to_string());
+            }
+            imp::set_affinity(*pid, cores).map(|_| "done".to_string())
+        }
+    }
+}
+
+fn signal(pid: u32, signal: Signal) -> Result<String, String> {
+    let mut system = System::new();
+    let pid = Pid::from_u32(pid);
+    if !system.refresh_process(pid) {
+        return Err("no such process".to_string());
+    }
+    match system.process(pid).map(|process| process.kill_with(signal)) {
+        Some(Some(true)) => Ok(format!("sent {signal}")),
+        Some(Some(false)) => Err(format!("failed to send {signal} (permission denied?)")),
+        Some(None) => Err(format!("{signal} is not supported on this platform")),
+        None => Err("no such process".to_string()),
+    }
+}
+
+pub use imp::{get_affinity, get_io_priority, get_nice};
+
+/// Formats cpu indices compactly, e.g. `[0, 1, 2, 5]` as `0-2,5`.
+pub fn format_cpu_list(cores: &[usize]) -> String {
+    let mut ranges: Vec<String> = Vec::new();
+    let mut iter = cores.iter().copied().peekable();
+    while let Some(start) = iter.next() {
+        let mut end = start;
+        while iter.peek() == Some(&(end + 1)) {
+            end = iter.next().unwrap_or(end);
+        }
+        ranges.push(if start == end { start.to_string() } else { format!("{start}-{end}") });
+    }
+    ranges.join(",")
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    use super::{IoClass, IoPriority};
+    use std::fs;
+    use std::io;
+
+    const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+    const IOPRIO_CLASS_SHIFT: u32 = 13;
+
+    // Explains the errno of the last failed call, calling out permission problems.
+    fn last_error() -> String {
+        let err = io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::EPERM) | Some(libc::EACCES) => format!("permission denied ({err}); try running as root"),
+            Some(libc::ESRCH) => "no such process".to_string(),
+            _ => err.to_string(),
+        }
+    }
+
+    pub fn get_nice(pid: u32) -> Option<i32> {
+        // Field 19 of /proc/<pid>/stat; the command name may contain spaces,
+        // so count from the closing parenthesis
+        let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        let (_, rest) = stat.rsplit_once(')')?;
+        rest.split_whitespace().nth(16)?.parse().ok()
+    }
+
+    pub fn set_nice(pid: u32, nice: i32) -> Result<(), String> {
+        if unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as libc::id_t, nice) } == 0 {
+            Ok(())
+        } else {
+            Err(last_error())
+        }
+    }
+
+    pub fn get_io_priority(pid: u32) -> Option<IoPriority> {
+        let value = unsafe { libc::syscall(libc::SYS_ioprio_get, IOPRIO_WHO_PROCESS, pid as libc::c_int) };
+        if value < 0 {
+            return None;
+        }
+        let class = match value >> IOPRIO_CLASS_SHIFT {
+            1 => IoClass::RealTime,
+            2 => IoClass::BestEffort,
+            3 => IoClass::Idle,
+            _ => IoClass::None,
+        };
+        Some(IoPriority { class, level: (value & 0xff) as u8 })
+    }
+
+    pub fn set_io_priority(pid: u32, priority: IoPriority) -> Result<(), String> {
+        let class: libc::c_long = match priority.class {
+            IoClass::None => 0,
+            IoClass::RealTime => 1,
+            IoClass::BestEffort => 2,
+            IoClass::Idle => 3,
+        };
+        let value = (class << IOPRIO_CLASS_SHIFT) | priority.level.min(7) as libc::c_long;
+        let result = unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, pid as libc::c_int, value) };
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(last_error())
+        }
+    }
+
+    pub fn get_affinity(pid: u32) -> Option<Vec<usize>> {
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        let size = std::mem::size_of::<libc::cpu_set_t>();
+        if unsafe { libc::sched_getaffinity(pid as libc::pid_t, size, &mut set) } != 0 {
+            return None;
+        }
+        Some((0..libc::CPU_SETSIZE as usize).filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) }).collect())
+    }
+
+    pub fn set_affinity(pid: u32, cores: &[usize]) -> Result<(), String> {
+        // CPU_SET writes out of bounds for indexes past the set's capacity
+        let configured = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) };
+        let limit = (libc::CPU_SETSIZE as usize).min(if configured > 0 { configured as usize } else { usize::MAX });
+        if let Some(&cpu) = cores.iter().find(|&&cpu| cpu >= limit) {
+            return Err(format!("cpu {cpu} does not exist (this machine has {limit})"));
+        }
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        for &cpu in cores {
+            unsafe { libc::CPU_SET(cpu, &mut set) };
+        }
+        let size = std::mem::size_of::<libc::cpu_set_t>();
+        if unsafe { libc::sched_setaffinity(pid as libc::pid_t, size, &set) } == 0 {
+            Ok(())
+        } else {
+            Err(last_error())
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod imp {
+    use super::IoPriority;
+
+    const UNSUPPORTED: &str = "not supported on this platform";
+
+    pub fn get_nice(_pid: u32) -> Option<i32> {
+        None
+    }
+
+    pub fn set_nice(_pid: u32, _nice: i32) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub fn get_io_priority(_pid: u32) -> Option<IoPriority> {
+        None
+    }
+
+    pub fn set_io_priority(_pid: u32, _priority: IoPriority) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+
+    pub fn get_affinity(_pid: u32) -> Option<Vec<usize>> {
+        None
+    }
+
+    pub fn set_affinity(_pid: u32, _cores: &[usize]) -> Result<(), String> {
+        Err(UNSUPPORTED.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn affinity_rejects_cpus_out_of_range() {
+        let pid = std::process::id();
+        for cpu in [libc::CPU_SETSIZE as usize, usize::MAX] {
+            let err = execute(&ProcessAction::Affinity { pid, cores: vec![0, cpu] }).unwrap_err();
+            assert!(err.contains("does not exist"), "{err}");
+        }
+    }
+}
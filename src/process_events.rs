@@ -0,0 +1,53 @@
+use chrono::{DateTime, Local};
+use std::collections::HashSet;
+
+use crate::app::ProcessInfo;
+
+/// Events kept for the events pane; older ones are dropped first.
+pub const MAX_EVENTS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Started,
+    Exited,
+}
+
+/// A process appearing or disappearing between two process list refreshes.
+#[derive(Debug, Clone)]
+pub struct ProcessEvent {
+    /// When the change was observed, i.e. at most one refresh after it happened
+    pub time: DateTime<Local>,
+    pub kind: EventKind,
+    pub pid: u32,
+    pub name: String,
+    /// Seconds the process ran for, known only for exits
+    pub lifetime: Option<u64>,
+}
+
+/// Compares two process lists. Processes are matched on pid and start time,
+/// so a pid reused by a new process is reported as an exit and a start.
+pub fn diff(previous: &[ProcessInfo], current: &[ProcessInfo], now: DateTime<Local>) -> Vec<ProcessEvent> {
+    let key = |process: &ProcessInfo| (process.pid, process.start_time);
+    let before: HashSet<_> = previous.iter().map(key).collect();
+    let after: HashSet<_> = current.iter().map(key).collect();
+
+    let exited = previous.iter().filter(|p| !after.contains(&key(p))).map(|process| ProcessEvent {
+        time: now,
+        kind: EventKind::Exited,
+        pid: process.pid,
+        name: process.name.clone(),
+        lifetime: (process.start_time > 0)
+            .then(|| (now.timestamp() as u64).saturating_sub(process.start_time)),
+    });
+    let started = current.iter().filter(|p| !before.contains(&key(p))).map(|process| ProcessEvent {
+        time: now,
+        kind: EventKind::Started,
+        pid: process.pid,
+        name: process.name.clone(),
+        lifetime: None,
+    });
+
+    let mut events: Vec<ProcessEvent> = exited.chain(started).collect();
+    events.sort_by_key(|event| event.pid);
+    events
+}
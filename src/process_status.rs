@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::app::ProcessInfo;
+
+/// A zombie still around after this long is assumed not to be reaped by its parent.
+const UNREAPED_AFTER: Duration = Duration::from_secs(5);
+
+/// Scheduler state of a process. Older agents sent sysinfo's `Debug` names
+/// (`Run`, `Sleep`, `Stop`, ...) or its display strings (`Runnable`, `Sleeping`,
+/// ...), which the aliases still accept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProcessStatus {
+    #[serde(alias = "Run", alias = "Runnable")]
+    Running,
+    #[serde(alias = "Sleep", alias = "Parked")]
+    Sleeping,
+    /// Uninterruptible sleep (`D`), usually waiting on disk or a network filesystem
+    #[serde(alias = "UninterruptibleDiskSleep")]
+    DiskSleep,
+    #[serde(alias = "Stop")]
+    Stopped,
+    Tracing,
+    Zombie,
+    Dead,
+    Idle,
+    #[serde(other)]
+    Unknown,
+}
+
+impl ProcessStatus {
+    /// States worth a look: zombies, D-state and stopped processes.
+    pub fn is_problem(self) -> bool {
+        matches!(self, ProcessStatus::Zombie | ProcessStatus::DiskSleep | ProcessStatus::Stopped)
+    }
+}
+
+impl From<sysinfo::ProcessStatus> for ProcessStatus {
+    fn from(status: sysinfo::ProcessStatus) -> Self {
+        match status {
+            sysinfo::ProcessStatus::Run => ProcessStatus::Running,
+            sysinfo::ProcessStatus::Sleep | sysinfo::ProcessStatus::Parked => ProcessStatus::Sleeping,
+            sysinfo::ProcessStatus::UninterruptibleDiskSleep => ProcessStatus::DiskSleep,
+            sysinfo::ProcessStatus::Stop => ProcessStatus::Stopped,
+            sysinfo::ProcessStatus::Tracing => ProcessStatus::Tracing,
+            sysinfo::ProcessStatus::Zombie => ProcessStatus::Zombie,
+            sysinfo::ProcessStatus::Dead => ProcessStatus::Dead,
+            sysinfo::ProcessStatus::Idle => ProcessStatus::Idle,
+            _ => ProcessStatus::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for ProcessStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ProcessStatus::Running => "Running",
+            ProcessStatus::Sleeping => "Sleeping",
+            ProcessStatus::DiskSleep => "Disk sleep (D)",
+            ProcessStatus::Stopped => "Stopped",
+            ProcessStatus::Tracing => "Tracing",
+            ProcessStatus::Zombie => "Zombie",
+            ProcessStatus::Dead => "Dead",
+            ProcessStatus::Idle => "Idle",
+            ProcessStatus::Unknown => "Unknown",
+        })
+    }
+}
+
+/// Processes in each problem state.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct StateCounts {
+    pub zombie: usize,
+    /// Zombies whose living parent has not reaped them
+    pub unreaped: usize,
+    pub disk_sleep: usize,
+    pub stopped: usize,
+}
+
+/// Remembers when each zombie was first seen, to tell a zombie about to be
+/// reaped from one its parent is ignoring.
+#[derive(Debug, Default)]
+pub struct ZombieTracker {
+    first_seen: HashMap<u32, Instant>,
+}
+
+impl ZombieTracker {
+    pub fn update(&mut self, processes: &[ProcessInfo]) {
+        let now = Instant::now();
+        let mut first_seen = HashMap::new();
+        for process in processes.iter().filter(|p| p.status == ProcessStatus::Zombie) {
+            let since = self.first_seen.get(&process.pid).copied().unwrap_or(now);
+            first_seen.insert(process.pid, since);
+        }
+        self.first_seen = first_seen;
+    }
+
+    /// The parent of `process` if it is a zombie that parent has left unreaped.
+    pub fn unreaped_parent<'a>(&self, process: &ProcessInfo, processes: &'a [ProcessInfo]) -> Option<&'a ProcessInfo> {
+        if process.status != ProcessStatus::Zombie {
+            return None;
+        }
+        let since = self.first_seen.get(&process.pid)?;
+        if since.elapsed() < UNREAPED_AFTER {
+            return None;
+        }
+        let parent = process.parent?;
+        processes.iter().find(|p| p.pid == parent)
+    }
+
+    pub fn counts(&self, processes: &[ProcessInfo]) -> StateCounts {
+        let mut counts = StateCounts::default();
+        for process in processes {
+            match process.status {
+                ProcessStatus::Zombie => {
+                    counts.zombie += 1;
+                    if self.unreaped_parent(process, processes).is_some() {
+                        counts.unreaped += 1;
+                    }
+                }
+                ProcessStatus::DiskSleep => counts.disk_sleep += 1,
+                ProcessStatus::Stopped => counts.stopped += 1,
+                _ => {}
+            }
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_statuses_from_old_agents() {
+        // As sent by agents that stored `format!("{:?}", process.status())`
+        let debug: Vec<ProcessStatus> = serde_json::from_str(
+            r#"["Run", "Sleep", "Stop", "Idle", "Zombie", "UninterruptibleDiskSleep", "Parked", "Unknown(42)"]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            debug,
+            [
+                ProcessStatus::Running,
+                ProcessStatus::Sleeping,
+                ProcessStatus::Stopped,
+                ProcessStatus::Idle,
+                ProcessStatus::Zombie,
+                ProcessStatus::DiskSleep,
+                ProcessStatus::Sleeping,
+                ProcessStatus::Unknown,
+            ]
+        );
+
+        // and by those that stored its display string
+        let display: Vec<ProcessStatus> =
+            serde_json::from_str(r#"["Runnable", "Sleeping", "Stopped", "Tracing"]"#).unwrap();
+        assert_eq!(
+            display,
+            [ProcessStatus::Running, ProcessStatus::Sleeping, ProcessStatus::Stopped, ProcessStatus::Tracing]
+        );
+    }
+
+    #[test]
+    fn old_agent_process_payload() {
+        let process: ProcessInfo = serde_json::from_str(
+            r#"{"pid": 42, "parent": 1, "name": "nginx", "cpu_usage": 1.5, "memory": 1024,
+                "status": "Sleep", "start_time": 0}"#,
+        )
+        .unwrap();
+        assert_eq!(process.status, ProcessStatus::Sleeping);
+    }
+}
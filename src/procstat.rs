@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::Instant;
+
+/// Cumulative time counters for one `cpu` line of `/proc/stat`, in clock ticks.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+}
+
+impl CpuTimes {
+    fn total(&self) -> u64 {
+        self.user + self.nice + self.system + self.idle + self.iowait + self.irq + self.softirq + self.steal
+    }
+
+    /// Percentage of time spent in each state between `previous` and `self`.
+    pub fn breakdown_since(&self, previous: &CpuTimes) -> CpuBreakdown {
+        let elapsed = self.total().saturating_sub(previous.total());
+        let percent = |now: u64, before: u64| {
+            if elapsed == 0 {
+                0.0
+            } else {
+                now.saturating_sub(before) as f32 / elapsed as f32 * 100.0
+            }
+        };
+
+        CpuBreakdown {
+            user: percent(self.user, previous.user),
+            nice: percent(self.nice, previous.nice),
+            system: percent(self.system, previous.system),
+            idle: percent(self.idle, previous.idle),
+            iowait: percent(self.iowait, previous.iowait),
+            irq: percent(self.irq, previous.irq),
+            softirq: percent(self.softirq, previous.softirq),
+            steal: percent(self.steal, previous.steal),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CpuBreakdown {
+    pub user: f32,
+    pub nice: f32,
+    pub system: f32,
+    pub idle: f32,
+    pub iowait: f32,
+    pub irq: f32,
+    pub softirq: f32,
+    pub steal: f32,
+}
+
+impl CpuBreakdown {
+    /// Busy states in display order, paired with their names; idle is left out.
+    pub fn busy(&self) -> [(&'static str, f32); 7] {
+        [
+            ("user", self.user),
+            ("nice", self.nice),
+            ("system", self.system),
+            ("iowait", self.iowait),
+            ("irq", self.irq),
+            ("softirq", self.softirq),
+            ("steal", self.steal),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CpuStats {
+    pub global: CpuBreakdown,
+    pub cores: Vec<CpuBreakdown>,
+    pub context_switches_per_sec: f64,
+    pub interrupts_per_sec: f64,
+    #[serde(default)]
+    pub forks_per_sec: f64,
+}
+
+/// One reading of `/proc/stat`; rates need two of them.
+#[derive(Debug, Clone)]
+pub struct StatSample {
+    pub global: CpuTimes,
+    pub cores: Vec<CpuTimes>,
+    pub context_switches: u64,
+    pub interrupts: u64,
+    pub forks: u64,
+    pub taken: Instant,
+}
+
+impl StatSample {
+    pub fn stats_since(&self, previous: &StatSample) -> CpuStats {
+        let seconds = self.taken.duration_since(previous.taken).as_secs_f64();
+        let rate = |now: u64, before: u64| {
+            if seconds > 0.0 {
+                now.saturating_sub(before) as f64 / seconds
+            } else {
+                0.0
+            }
+        };
+
+        CpuStats {
+            global: self.global.breakdown_since(&previous.global),
+            cores: self
+                .cores
+                .iter()
+                .zip(&previous.cores)
+                .map(|(now, before)| now.breakdown_since(before))
+                .collect(),
+            context_switches_per_sec: rate(self.context_switches, previous.context_switches),
+            interrupts_per_sec: rate(self.interrupts, previous.interrupts),
+            forks_per_sec: rate(self.forks, previous.forks),
+        }
+    }
+}
+
+pub fn read() -> Option<StatSample> {
+    fs::read_to_string("/proc/stat").ok().and_then(|contents| parse_stat(&contents))
+}
+
+/// Parses `/proc/stat`, returning `None` if the aggregate `cpu` line is missing.
+pub fn parse_stat(contents: &str) -> Option<StatSample> {
+    let mut global = None;
+    let mut cores = Vec::new();
+    let mut context_switches = 0;
+    let mut interrupts = 0;
+    let mut forks = 0;
+
+    for line in contents.lines() {
+        let mut parts = line.split_whitespace();
+        match parts.next() {
+            Some("cpu") => global = Some(parse_cpu_times(parts)),
+            Some(name) if name.starts_with("cpu") => cores.push(parse_cpu_times(parts)),
+            Some("ctxt") => context_switches = parts.next().and_then(|v| v.parse().ok()).unwrap_or(0),
+            // The first number on the `intr` line is the total across all interrupts
+            Some("intr") => interrupts = parts.next().and_then(|v| v.parse().ok()).unwrap_or(0),
+            Some("processes") => forks = parts.next().and_then(|v| v.parse().ok()).unwrap_or(0),
+            _ => {}
+        }
+    }
+
+    Some(StatSample {
+        global: global?,
+        cores,
+        context_switches,
+        interrupts,
+        forks,
+        taken: Instant::now(),
+    })
+}
+
+fn parse_cpu_times<'a>(parts: impl Iterator<Item = &'a str>) -> CpuTimes {
+    let values: Vec<u64> = parts.map(|v| v.parse().unwrap_or(0)).collect();
+    let value = |index: usize| values.get(index).copied().unwrap_or(0);
+    CpuTimes {
+        user: value(0),
+        nice: value(1),
+        system: value(2),
+        idle: value(3),
+        iowait: value(4),
+        irq: value(5),
+        softirq: value(6),
+        steal: value(7),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAT: &str = "\
+cpu  1000 50 300 8000 200 10 20 5 0 0
+cpu0 500 25 150 4000 100 5 10 3 0 0
+cpu1 500 25 150 4000 100 5 10 2 0 0
+intr 123456 9 0 0 0
+ctxt 987654
+btime 1700000000
+processes 4321
+procs_running 3
+procs_blocked 1
+softirq 5555 0 1 2 3
+";
+
+    #[test]
+    fn parses_cpu_lines_and_counters() {
+        let sample = parse_stat(STAT).unwrap();
+        assert_eq!(sample.global.user, 1000);
+        assert_eq!(sample.global.steal, 5);
+        assert_eq!(sample.cores.len(), 2);
+        assert_eq!(sample.cores[1].steal, 2);
+        assert_eq!(sample.context_switches, 987654);
+        assert_eq!(sample.interrupts, 123456);
+        // `processes` counts forks since boot; procs_running and procs_blocked are not it
+        assert_eq!(sample.forks, 4321);
+    }
+
+    #[test]
+    fn missing_aggregate_cpu_line_is_rejected() {
+        assert!(parse_stat("cpu0 1 2 3 4\nctxt 5\n").is_none());
+    }
+
+    #[test]
+    fn breakdown_and_rates_between_samples() {
+        let before = parse_stat(STAT).unwrap();
+        let mut after = parse_stat("cpu  1060 50 320 8100 220 10 20 5\nintr 123556\nctxt 988654\nprocesses 4331\n").unwrap();
+        after.taken = before.taken + std::time::Duration::from_secs(2);
+
+        let stats = after.stats_since(&before);
+        // 60 of the 200 elapsed ticks in user
+        let shares = [stats.global.user, stats.global.system, stats.global.idle, stats.global.iowait];
+        for (share, expected) in shares.into_iter().zip([30.0, 10.0, 50.0, 10.0]) {
+            assert!((share - expected).abs() < 0.001, "{share} != {expected}");
+        }
+        assert_eq!(stats.context_switches_per_sec, 500.0);
+        assert_eq!(stats.interrupts_per_sec, 50.0);
+        assert_eq!(stats.forks_per_sec, 5.0);
+    }
+}
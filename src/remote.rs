@@ -0,0 +1,575 @@
+use anyhow::{anyhow, bail, Result};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, watch};
+
+use crate::app::{App, ProcessInfo, SystemInfo};
+use crate::config::AgentConfig;
+use crate::interfaces::Interface;
+use crate::meminfo::MemoryBreakdown;
+use crate::plugins::PluginState;
+use crate::power::PowerStatus;
+use crate::pressure::PressureStats;
+use crate::process_control::{self, ProcessAction};
+use crate::procstat::{CpuBreakdown, CpuStats};
+use crate::sensors::Sensor;
+use crate::services::ServiceInfo;
+use crate::transport::{Acceptor, BoxStream, Connector};
+
+const MAX_FRAME_LEN: u32 = 64 * 1024 * 1024;
+/// Limit for the unauthenticated `Hello`, which only carries a token.
+const MAX_HELLO_LEN: u32 = 4 * 1024;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Everything the TUI needs to render one host.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub system_info: SystemInfo,
+    pub processes: Vec<ProcessInfo>,
+    pub services: Vec<ServiceInfo>,
+    pub cpu_history: Vec<f32>,
+    pub memory_history: Vec<f32>,
+    #[serde(default)]
+    pub swap_history: Vec<f32>,
+    #[serde(default)]
+    pub memory_breakdown: Option<MemoryBreakdown>,
+    #[serde(default)]
+    pub pressure: Option<PressureStats>,
+    #[serde(default)]
+    pub pressure_history: Vec<(f32, f32, f32)>,
+    #[serde(default)]
+    pub power: Option<PowerStatus>,
+    #[serde(default)]
+    pub battery_history: Vec<f32>,
+    #[serde(default)]
+    pub sensors: Vec<Sensor>,
+    #[serde(default)]
+    pub cpu_stats: Option<CpuStats>,
+    #[serde(default)]
+    pub cpu_breakdown_history: Vec<CpuBreakdown>,
+    pub network_history: Vec<(u64, u64)>,
+    pub disk_usage: Vec<(String, u64, u64)>,
+    pub interface_count: usize,
+    #[serde(default)]
+    pub interfaces: Vec<Interface>,
+    pub interval_ms: u64,
+    #[serde(default)]
+    pub custom_metrics: Vec<PluginState>,
+}
+
+impl Snapshot {
+    pub fn from_app(app: &App, interval: Duration) -> Self {
+        Self {
+            system_info: app.get_system_info(),
+            processes: app.processes.clone(),
+            services: app.services.clone(),
+            cpu_history: app.cpu_history.clone(),
+            memory_history: app.memory_history.clone(),
+            swap_history: app.swap_history.clone(),
+            memory_breakdown: app.memory_breakdown.clone(),
+            pressure: app.pressure.clone(),
+            pressure_history: app.pressure_history.clone(),
+            power: app.power.clone(),
+            battery_history: app.battery_history.clone(),
+            sensors: app.sensors.clone(),
+            cpu_stats: app.cpu_stats.clone(),
+            cpu_breakdown_history: app.cpu_breakdown_history.clone(),
+            network_history: app.network_history.clone(),
+            disk_usage: app.disk_usage.clone(),
+            interface_count: app.networks.len(),
+            interfaces: app.interfaces.clone(),
+            interval_ms: interval.as_millis() as u64,
+            custom_metrics: app.custom_metrics.clone(),
+        }
+    }
+}
+
+/// Frames sent from a monitor to an agent. `Hello` must come first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ClientMessage {
+    Hello { token: Option<String> },
+    Action(ProcessAction),
+}
+
+/// Frames sent from an agent to a monitor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AgentMessage {
+    Welcome { allow_control: bool },
+    Rejected { reason: String },
+    Snapshot(Box<Snapshot>),
+    ActionResult { success: bool, message: String },
+}
+
+/// Writes `value` as a big-endian `u32` length followed by its JSON encoding.
+pub async fn write_frame<W, T>(writer: &mut W, value: &T) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let body = serde_json::to_vec(value)?;
+    writer.write_u32(body.len() as u32).await?;
+    writer.write_all(&body).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+pub async fn read_frame<R, T>(reader: &mut R) -> Result<T>
+where
+    R: AsyncRead + Unpin,
+    T: DeserializeOwned,
+{
+    read_frame_limited(reader, MAX_FRAME_LEN).await
+}
+
+/// Reads a frame, refusing any longer than `limit` before allocating for it.
+async fn read_frame_limited<R, T>(reader: &mut R, limit: u32) -> Result<T>
+where
+    R: AsyncRead + Unpin,
+    T: DeserializeOwned,
+{
+    let len = reader.read_u32().await?;
+    if len > limit {
+        bail!("frame of {} bytes exceeds the {} byte limit", len, limit);
+    }
+    let mut body = vec![0; len as usize];
+    reader.read_exact(&mut body).await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Samples `app` every `interval` and streams snapshots to every authenticated client.
+pub async fn run_agent(listen: SocketAddr, mut app: App, interval: Duration, config: AgentConfig) -> Result<()> {
+    let acceptor = Acceptor::new(config.tls.as_ref())?;
+    let listener = TcpListener::bind(listen).await?;
+    eprintln!(
+        "agent listening on {} ({}, {}, control {})",
+        listener.local_addr()?,
+        if acceptor.is_tls() { "tls" } else { "plaintext" },
+        if config.token.is_some() { "token auth" } else { "no auth" },
+        if config.allow_control { "allowed" } else { "disabled" },
+    );
+    if !acceptor.is_tls() && config.token.is_none() && !listen.ip().is_loopback() {
+        eprintln!("warning: anyone who can reach {listen} can read this machine's data");
+    }
+
+    app.update().await;
+    let (tx, rx) = watch::channel(Snapshot::from_app(&app, interval));
+    let policy = Arc::new(AgentPolicy {
+        token: config.token,
+        allow_control: config.allow_control,
+        audit_log: config.audit_log,
+    });
+
+    tokio::spawn(accept_clients(listener, acceptor, rx, policy));
+
+    loop {
+        tokio::time::sleep(interval).await;
+        app.update().await;
+        tx.send_replace(Snapshot::from_app(&app, interval));
+    }
+}
+
+/// Hands every incoming connection to its own `serve_client` task.
+async fn accept_clients(
+    listener: TcpListener,
+    acceptor: Acceptor,
+    rx: watch::Receiver<Snapshot>,
+    policy: Arc<AgentPolicy>,
+) {
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(err) => {
+                eprintln!("accept failed: {err}");
+                continue;
+            }
+        };
+        let acceptor = acceptor.clone();
+        let policy = policy.clone();
+        let rx = rx.clone();
+        tokio::spawn(async move {
+            let result = match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                Ok(Ok(stream)) => serve_client(stream, peer, rx, policy).await,
+                Ok(Err(err)) => Err(err),
+                Err(_) => Err(anyhow!("TLS handshake timed out")),
+            };
+            if let Err(err) = result {
+                eprintln!("client {peer} disconnected: {err}");
+            }
+        });
+    }
+}
+
+struct AgentPolicy {
+    token: Option<String>,
+    allow_control: bool,
+    audit_log: Option<PathBuf>,
+}
+
+impl AgentPolicy {
+    fn authorize(&self, hello: &ClientMessage) -> bool {
+        match (&self.token, hello) {
+            (None, ClientMessage::Hello { .. }) => true,
+            (Some(expected), ClientMessage::Hello { token: Some(token) }) => tokens_match(expected, token),
+            _ => false,
+        }
+    }
+
+    /// Appends one line per remote action to the audit log and stderr.
+    fn audit(&self, peer: SocketAddr, action: &str, outcome: &str) {
+        let line = format!("{} peer={} action=\"{}\" result=\"{}\"", chrono::Utc::now().to_rfc3339(), peer, action, outcome);
+        eprintln!("audit: {line}");
+        if let Some(path) = &self.audit_log {
+            let written = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| writeln!(file, "{line}"));
+            if let Err(err) = written {
+                eprintln!("failed to write audit log {}: {err}", path.display());
+            }
+        }
+    }
+
+    fn handle(&self, peer: SocketAddr, message: ClientMessage) -> AgentMessage {
+        let (action, outcome) = match message {
+            ClientMessage::Hello { .. } => ("hello".to_string(), Err("already authenticated".to_string())),
+            ClientMessage::Action(action) => {
+                let outcome = if self.allow_control {
+                    process_control::execute(&action)
+                } else {
+                    Err("control is not allowed by this agent".to_string())
+                };
+                (action.describe(), outcome)
+            }
+        };
+
+        let (success, detail) = match outcome {
+            Ok(detail) => (true, detail),
+            Err(detail) => (false, detail),
+        };
+        self.audit(peer, &action, &detail);
+        AgentMessage::ActionResult { success, message: format!("{action}: {detail}") }
+    }
+}
+
+// Compares every byte so the time taken does not reveal the matching prefix.
+fn tokens_match(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected.bytes().zip(given.bytes()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+async fn serve_client(
+    stream: BoxStream,
+    peer: SocketAddr,
+    mut rx: watch::Receiver<Snapshot>,
+    policy: Arc<AgentPolicy>,
+) -> Result<()> {
+    let (mut reader, mut writer) = tokio::io::split(stream);
+
+    let hello = tokio::time::timeout(HANDSHAKE_TIMEOUT, read_frame_limited::<_, ClientMessage>(&mut reader, MAX_HELLO_LEN))
+        .await
+        .map_err(|_| anyhow!("handshake timed out"))??;
+    if !policy.authorize(&hello) {
+        policy.audit(peer, "authenticate", "invalid token");
+        let reason = "invalid token".to_string();
+        write_frame(&mut writer, &AgentMessage::Rejected { reason }).await?;
+        bail!("authentication failed");
+    }
+    let welcome = AgentMessage::Welcome { allow_control: policy.allow_control };
+    write_frame(&mut writer, &welcome).await?;
+
+    let (result_tx, mut result_rx) = mpsc::unbounded_channel();
+    let reader_task = tokio::spawn(async move {
+        while let Ok(message) = read_frame::<_, ClientMessage>(&mut reader).await {
+            if result_tx.send(policy.handle(peer, message)).is_err() {
+                break;
+            }
+        }
+    });
+
+    let outcome: Result<()> = async {
+        loop {
+            let snapshot = Box::new(rx.borrow_and_update().clone());
+            write_frame(&mut writer, &AgentMessage::Snapshot(snapshot)).await?;
+            loop {
+                tokio::select! {
+                    changed = rx.changed() => {
+                        changed?;
+                        break;
+                    }
+                    Some(result) = result_rx.recv() => write_frame(&mut writer, &result).await?,
+                    else => bail!("client closed the connection"),
+                }
+            }
+        }
+    }
+    .await;
+
+    reader_task.abort();
+    outcome
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionStatus {
+    Connecting,
+    Connected,
+    Disconnected { retry_in: Duration, error: String },
+}
+
+#[derive(Debug)]
+pub struct HostState {
+    pub address: String,
+    pub status: ConnectionStatus,
+    pub snapshot: Option<Snapshot>,
+    pub last_update: Option<Instant>,
+    pub allow_control: bool,
+    pub last_action: Option<String>,
+    pub control: mpsc::UnboundedSender<ClientMessage>,
+}
+
+impl HostState {
+    /// Data is stale once three agent intervals pass without a new snapshot.
+    pub fn is_stale(&self) -> bool {
+        match (&self.snapshot, self.last_update) {
+            (Some(snapshot), Some(last_update)) => {
+                let interval = Duration::from_millis(snapshot.interval_ms.max(1000));
+                self.status != ConnectionStatus::Connected || last_update.elapsed() > interval * 3
+            }
+            _ => true,
+        }
+    }
+
+    /// Queues an action for the agent, refusing it unless the agent allows control.
+    pub fn send_action(&mut self, message: ClientMessage) {
+        if self.status != ConnectionStatus::Connected {
+            self.last_action = Some("not connected to the agent".to_string());
+        } else if !self.allow_control {
+            self.last_action = Some("control is not allowed by this agent".to_string());
+        } else if self.control.send(message).is_err() {
+            self.last_action = Some("connection task has stopped".to_string());
+        }
+    }
+}
+
+pub type RemoteHost = Arc<Mutex<HostState>>;
+
+/// Starts a background task that keeps a connection to `address` open,
+/// reconnecting with exponential backoff.
+pub fn spawn_client(address: String, connector: Connector, token: Option<String>) -> RemoteHost {
+    let (control, mut commands) = mpsc::unbounded_channel();
+    let host = Arc::new(Mutex::new(HostState {
+        address: address.clone(),
+        status: ConnectionStatus::Connecting,
+        snapshot: None,
+        last_update: None,
+        allow_control: false,
+        last_action: None,
+        control,
+    }));
+
+    let state = host.clone();
+    tokio::spawn(async move {
+        let mut backoff = INITIAL_BACKOFF;
+        loop {
+            set_status(&state, ConnectionStatus::Connecting);
+            let error = match run_session(&connector, &address, token.clone(), &state, &mut commands, &mut backoff).await {
+                Ok(()) => "connection closed by agent".to_string(),
+                Err(err) => err.to_string(),
+            };
+
+            set_status(&state, ConnectionStatus::Disconnected { retry_in: backoff, error });
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    });
+
+    host
+}
+
+async fn run_session(
+    connector: &Connector,
+    address: &str,
+    token: Option<String>,
+    state: &RemoteHost,
+    commands: &mut mpsc::UnboundedReceiver<ClientMessage>,
+    backoff: &mut Duration,
+) -> Result<()> {
+    let stream = connector.connect(address).await?;
+    let (mut reader, mut writer) = tokio::io::split(stream);
+
+    write_frame(&mut writer, &ClientMessage::Hello { token }).await?;
+    let allow_control = match read_frame::<_, AgentMessage>(&mut reader).await? {
+        AgentMessage::Welcome { allow_control } => allow_control,
+        AgentMessage::Rejected { reason } => bail!("rejected by agent: {reason}"),
+        _ => bail!("unexpected handshake reply"),
+    };
+
+    *backoff = INITIAL_BACKOFF;
+    {
+        let mut host = state.lock().unwrap();
+        host.status = ConnectionStatus::Connected;
+        host.allow_control = allow_control;
+    }
+    // Actions queued while disconnected may target pids that have since been reused.
+    while commands.try_recv().is_ok() {}
+
+    let (frame_tx, mut frames) = mpsc::unbounded_channel();
+    let reader_task = tokio::spawn(async move {
+        loop {
+            let frame = read_frame::<_, AgentMessage>(&mut reader).await;
+            let failed = frame.is_err();
+            if frame_tx.send(frame).is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    let outcome: Result<()> = async {
+        loop {
+            tokio::select! {
+                frame = frames.recv() => match frame {
+                    Some(Ok(message)) => apply_message(state, message),
+                    Some(Err(err)) => return Err(err),
+                    None => return Ok(()),
+                },
+                Some(command) = commands.recv() => write_frame(&mut writer, &command).await?,
+            }
+        }
+    }
+    .await;
+
+    reader_task.abort();
+    outcome
+}
+
+fn apply_message(state: &RemoteHost, message: AgentMessage) {
+    let mut host = state.lock().unwrap();
+    match message {
+        AgentMessage::Snapshot(snapshot) => {
+            host.snapshot = Some(*snapshot);
+            host.last_update = Some(Instant::now());
+        }
+        AgentMessage::ActionResult { success, message } => {
+            host.last_action = Some(if success { message } else { format!("failed: {message}") });
+        }
+        AgentMessage::Welcome { .. } | AgentMessage::Rejected { .. } => {}
+    }
+}
+
+fn set_status(host: &RemoteHost, status: ConnectionStatus) {
+    host.lock().unwrap().status = status;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AgentTlsConfig, ClientTlsConfig};
+
+    /// Starts an agent on a free localhost port serving a snapshot of a fresh `App`.
+    async fn start_agent(acceptor: Acceptor, token: Option<String>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let (tx, rx) = watch::channel(Snapshot::from_app(&App::new(), Duration::from_secs(1)));
+        let policy = Arc::new(AgentPolicy { token, allow_control: false, audit_log: None });
+        tokio::spawn(async move {
+            // Keeps the sender, and with it the snapshot stream, alive for the test
+            let _tx = tx;
+            accept_clients(listener, acceptor, rx, policy).await;
+        });
+        address
+    }
+
+    #[tokio::test]
+    async fn frames_round_trip() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        let hello = ClientMessage::Hello { token: Some("secret".to_string()) };
+        write_frame(&mut client, &hello).await.unwrap();
+        match read_frame::<_, ClientMessage>(&mut server).await.unwrap() {
+            ClientMessage::Hello { token } => assert_eq!(token.as_deref(), Some("secret")),
+            other => panic!("unexpected frame {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn oversized_frame_is_rejected() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        client.write_u32(MAX_FRAME_LEN + 1).await.unwrap();
+        let err = read_frame::<_, ClientMessage>(&mut server).await.unwrap_err();
+        assert!(err.to_string().contains("exceeds"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn localhost_session_gets_welcome_then_snapshot() {
+        let address = start_agent(Acceptor::new(None).unwrap(), None).await;
+        let stream = Connector::new(None).unwrap().connect(&address.to_string()).await.unwrap();
+        let (mut reader, mut writer) = tokio::io::split(stream);
+
+        write_frame(&mut writer, &ClientMessage::Hello { token: None }).await.unwrap();
+        match read_frame::<_, AgentMessage>(&mut reader).await.unwrap() {
+            AgentMessage::Welcome { allow_control } => assert!(!allow_control),
+            other => panic!("expected Welcome, got {other:?}"),
+        }
+        match read_frame::<_, AgentMessage>(&mut reader).await.unwrap() {
+            AgentMessage::Snapshot(snapshot) => assert_eq!(snapshot.interval_ms, 1000),
+            other => panic!("expected Snapshot, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn wrong_token_is_rejected() {
+        let address = start_agent(Acceptor::new(None).unwrap(), Some("secret".to_string())).await;
+        let stream = Connector::new(None).unwrap().connect(&address.to_string()).await.unwrap();
+        let (mut reader, mut writer) = tokio::io::split(stream);
+
+        write_frame(&mut writer, &ClientMessage::Hello { token: Some("guess".to_string()) }).await.unwrap();
+        match read_frame::<_, AgentMessage>(&mut reader).await.unwrap() {
+            AgentMessage::Rejected { reason } => assert_eq!(reason, "invalid token"),
+            other => panic!("expected Rejected, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn oversized_hello_is_refused() {
+        let address = start_agent(Acceptor::new(None).unwrap(), None).await;
+        let stream = Connector::new(None).unwrap().connect(&address.to_string()).await.unwrap();
+        let (mut reader, mut writer) = tokio::io::split(stream);
+
+        let token = "x".repeat(MAX_HELLO_LEN as usize);
+        write_frame(&mut writer, &ClientMessage::Hello { token: Some(token) }).await.unwrap();
+        assert!(read_frame::<_, AgentMessage>(&mut reader).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn tls_session_with_self_signed_certificate() {
+        let dir = tempfile::tempdir().unwrap();
+        let generated = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let cert = dir.path().join("agent.crt");
+        let key = dir.path().join("agent.key");
+        std::fs::write(&cert, generated.cert.pem()).unwrap();
+        std::fs::write(&key, generated.key_pair.serialize_pem()).unwrap();
+
+        let agent_tls = AgentTlsConfig { cert: cert.clone(), key, client_ca: None };
+        let address = start_agent(Acceptor::new(Some(&agent_tls)).unwrap(), None).await;
+        let client_tls = ClientTlsConfig {
+            ca: cert,
+            cert: None,
+            key: None,
+            server_name: Some("localhost".to_string()),
+        };
+        let stream = Connector::new(Some(&client_tls)).unwrap().connect(&address.to_string()).await.unwrap();
+        let (mut reader, mut writer) = tokio::io::split(stream);
+
+        write_frame(&mut writer, &ClientMessage::Hello { token: None }).await.unwrap();
+        assert!(matches!(read_frame::<_, AgentMessage>(&mut reader).await.unwrap(), AgentMessage::Welcome { .. }));
+        assert!(matches!(read_frame::<_, AgentMessage>(&mut reader).await.unwrap(), AgentMessage::Snapshot(_)));
+    }
+}
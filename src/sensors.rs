@@ -0,0 +1,258 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+pub const DEFAULT_DIR: &str = "/sys/class";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SensorKind {
+    Temperature,
+    Fan,
+    Voltage,
+}
+
+impl SensorKind {
+    pub fn unit(self) -> &'static str {
+        match self {
+            SensorKind::Temperature => "°C",
+            SensorKind::Fan => "RPM",
+            SensorKind::Voltage => "V",
+        }
+    }
+
+    /// hwmon file prefix and the divisor from its raw units (millidegrees,
+    /// RPM, millivolts) to ours.
+    fn hwmon(self) -> (&'static str, f64) {
+        match self {
+            SensorKind::Temperature => ("temp", 1000.0),
+            SensorKind::Fan => ("fan", 1.0),
+            SensorKind::Voltage => ("in", 1000.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sensor {
+    /// hwmon driver name such as `coretemp` or `nct6775`, or `thermal` for thermal
+    /// zones. Later chips with a name already taken get `#2`, `#3`, ... appended.
+    pub chip: String,
+    /// Thermal zones sharing a type are told apart the same way
+    pub label: String,
+    pub kind: SensorKind,
+    pub value: f64,
+    pub max: Option<f64>,
+    pub critical: Option<f64>,
+    pub history: Vec<f64>,
+}
+
+/// How close a reading is to its limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Normal,
+    /// At or above the max, or within 10% of critical when there is no max
+    High,
+    Critical,
+}
+
+impl Sensor {
+    pub fn level(&self) -> Level {
+        // Fans have minimums rather than maximums worth warning about
+        if self.kind == SensorKind::Fan {
+            return Level::Normal;
+        }
+        let high = self.max.or(self.critical.map(|critical| critical * 0.9));
+        if self.critical.is_some_and(|critical| self.value >= critical) {
+            Level::Critical
+        } else if high.is_some_and(|high| self.value >= high) {
+            Level::High
+        } else {
+            Level::Normal
+        }
+    }
+}
+
+/// Reads every sensor under `dir`, laid out like `/sys/class`: the chips in
+/// `hwmon/`, then the thermal zones in `thermal/` that hwmon does not already cover.
+/// History is carried over from `previous` for sensors that are still present.
+pub fn read(dir: &Path, previous: &[Sensor]) -> Vec<Sensor> {
+    let mut sensors = Vec::new();
+    // Two NVMe drives both register an `nvme` chip
+    let mut chips = HashMap::new();
+    for chip in sorted_entries(&dir.join("hwmon")) {
+        let name = read_trimmed(&chip.join("name")).unwrap_or_else(|| file_name(&chip));
+        let name = disambiguate(name, &mut chips);
+        for kind in [SensorKind::Temperature, SensorKind::Fan, SensorKind::Voltage] {
+            read_hwmon(&chip, &name, kind, &mut sensors);
+        }
+    }
+    // Several zones may share a type, such as `acpitz`
+    let mut zones = HashMap::new();
+    for zone in sorted_entries(&dir.join("thermal")) {
+        if !file_name(&zone).starts_with("thermal_zone") || has_hwmon(&zone) {
+            continue;
+        }
+        let Some(value) = read_number(&zone.join("temp")) else {
+            continue;
+        };
+        sensors.push(Sensor {
+            chip: "thermal".to_string(),
+            label: disambiguate(read_trimmed(&zone.join("type")).unwrap_or_else(|| file_name(&zone)), &mut zones),
+            kind: SensorKind::Temperature,
+            value: value / 1000.0,
+            max: trip_point(&zone, "hot"),
+            critical: trip_point(&zone, "critical"),
+            history: Vec::new(),
+        });
+    }
+
+    for sensor in &mut sensors {
+        if let Some(old) = previous.iter().find(|old| old.chip == sensor.chip && old.label == sensor.label) {
+            sensor.history = old.history.clone();
+        }
+        sensor.history.push(sensor.value);
+        if sensor.history.len() > 60 {
+            sensor.history.remove(0);
+        }
+    }
+    sensors
+}
+
+/// Returns `name`, or `name#N` when it is the Nth time `name` is seen.
+fn disambiguate(name: String, seen: &mut HashMap<String, usize>) -> String {
+    let count = seen.entry(name.clone()).or_insert(0);
+    *count += 1;
+    if *count == 1 { name } else { format!("{}#{}", name, count) }
+}
+
+/// Highest current temperature of any sensor.
+pub fn max_temperature(sensors: &[Sensor]) -> Option<f64> {
+    sensors
+        .iter()
+        .filter(|sensor| sensor.kind == SensorKind::Temperature)
+        .map(|sensor| sensor.value)
+        .reduce(f64::max)
+}
+
+/// Adds the `temp1_input`, `temp2_input`, ... readings of one chip.
+fn read_hwmon(chip: &Path, name: &str, kind: SensorKind, sensors: &mut Vec<Sensor>) {
+    let (prefix, divisor) = kind.hwmon();
+    let mut indexes: Vec<u32> = fs::read_dir(chip)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let file = entry.file_name().to_string_lossy().into_owned();
+            file.strip_prefix(prefix)?.strip_suffix("_input")?.parse().ok()
+        })
+        .collect();
+    indexes.sort_unstable();
+    for index in indexes {
+        let field = |suffix: &str| chip.join(format!("{}{}_{}", prefix, index, suffix));
+        let Some(value) = read_number(&field("input")) else {
+            continue;
+        };
+        sensors.push(Sensor {
+            chip: name.to_string(),
+            label: read_trimmed(&field("label")).unwrap_or_else(|| format!("{}{}", prefix, index)),
+            kind,
+            value: value / divisor,
+            max: read_number(&field("max")).map(|max| max / divisor),
+            critical: read_number(&field("crit")).map(|critical| critical / divisor),
+            history: Vec::new(),
+        });
+    }
+}
+
+/// Temperature of the first trip point of `kind` ("hot", "critical", ...).
+fn trip_point(zone: &Path, kind: &str) -> Option<f64> {
+    (0..16).find_map(|index| {
+        let trip_type = read_trimmed(&zone.join(format!("trip_point_{}_type", index)))?;
+        (trip_type == kind).then(|| read_number(&zone.join(format!("trip_point_{}_temp", index))))?
+    }).map(|temp| temp / 1000.0)
+}
+
+/// Thermal zones registered with hwmon have a `hwmonN` directory of their own.
+fn has_hwmon(zone: &Path) -> bool {
+    sorted_entries(zone).iter().any(|entry| file_name(entry).starts_with("hwmon"))
+}
+
+fn sorted_entries(dir: &Path) -> Vec<std::path::PathBuf> {
+    let mut entries: Vec<_> = fs::read_dir(dir).into_iter().flatten().flatten().map(|entry| entry.path()).collect();
+    entries.sort();
+    entries
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+fn read_number(path: &Path) -> Option<f64> {
+    read_trimmed(path)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates `dir/<path>` with one file per `(file, contents)` pair.
+    fn write(dir: &Path, path: &str, files: &[(&str, &str)]) {
+        let path = dir.join(path);
+        fs::create_dir_all(&path).unwrap();
+        for (file, contents) in files {
+            fs::write(path.join(file), format!("{}\n", contents)).unwrap();
+        }
+    }
+
+    #[test]
+    fn reads_hwmon_chips_and_thermal_zones() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "hwmon/hwmon0",
+            &[
+                ("name", "coretemp"),
+                ("temp1_input", "45000"),
+                ("temp1_label", "Package id 0"),
+                ("temp1_max", "80000"),
+                ("temp1_crit", "100000"),
+                ("fan1_input", "1200"),
+                ("in0_input", "1250"),
+            ],
+        );
+        write(dir.path(), "thermal/thermal_zone0", &[("type", "acpitz"), ("temp", "27800")]);
+        // Covered by hwmon already
+        write(dir.path(), "thermal/thermal_zone1", &[("type", "x86_pkg_temp"), ("temp", "45000")]);
+        write(dir.path(), "thermal/thermal_zone1/hwmon3", &[]);
+
+        let sensors = read(dir.path(), &[]);
+        let readings: Vec<_> = sensors.iter().map(|s| (s.chip.as_str(), s.label.as_str(), s.kind, s.value)).collect();
+        assert_eq!(
+            readings,
+            [
+                ("coretemp", "Package id 0", SensorKind::Temperature, 45.0),
+                ("coretemp", "fan1", SensorKind::Fan, 1200.0),
+                ("coretemp", "in0", SensorKind::Voltage, 1.25),
+                ("thermal", "acpitz", SensorKind::Temperature, 27.8),
+            ]
+        );
+        assert_eq!((sensors[0].max, sensors[0].critical), (Some(80.0), Some(100.0)));
+        assert_eq!(max_temperature(&sensors), Some(45.0));
+    }
+
+    #[test]
+    fn duplicate_chips_keep_separate_histories() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "hwmon/hwmon0", &[("name", "nvme"), ("temp1_input", "30000"), ("temp1_label", "Composite")]);
+        write(dir.path(), "hwmon/hwmon1", &[("name", "nvme"), ("temp1_input", "50000"), ("temp1_label", "Composite")]);
+
+        let first = read(dir.path(), &[]);
+        let second = read(dir.path(), &first);
+        let histories: Vec<_> = second.iter().map(|s| (s.chip.as_str(), s.history.clone())).collect();
+        assert_eq!(histories, [("nvme", vec![30.0, 30.0]), ("nvme#2", vec![50.0, 50.0])]);
+    }
+}
@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::app::ProcessInfo;
+use crate::cgroup;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceInfo {
+    pub name: String,
+    pub main_pid: Option<u32>,
+    pub pids: Vec<u32>,
+    pub cpu_usage: f32,
+    pub memory: u64,
+    pub tasks: usize,
+}
+
+/// Finds `system.slice` in the unified hierarchy, or in the named systemd
+/// hierarchy on cgroup v1 hosts.
+pub fn system_slice() -> Option<PathBuf> {
+    cgroup::unified_mount()
+        .map(|mount| mount.join("system.slice"))
+        .into_iter()
+        .chain(std::iter::once(PathBuf::from("/sys/fs/cgroup/systemd/system.slice")))
+        .find(|path| path.is_dir())
+}
+
+/// Lists the units under `slice` and aggregates their processes' usage.
+pub fn collect(slice: &Path, processes: &[ProcessInfo]) -> Vec<ServiceInfo> {
+    let entries = match fs::read_dir(slice) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut services: Vec<ServiceInfo> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.ends_with(".service") {
+                return None;
+            }
+            let mut pids = Vec::new();
+            read_procs(&entry.path(), &mut pids);
+            pids.sort_unstable();
+            pids.dedup();
+            Some(build_service(name, pids, read_tasks(&entry.path()), processes))
+        })
+        .collect();
+
+    services.sort_by(|a, b| a.name.cmp(&b.name));
+    services
+}
+
+fn build_service(name: String, pids: Vec<u32>, tasks: Option<usize>, processes: &[ProcessInfo]) -> ServiceInfo {
+    let members: HashSet<u32> = pids.iter().copied().collect();
+    let mut cpu_usage = 0.0;
+    let mut memory = 0;
+    let mut main_pid = None;
+
+    for process in processes.iter().filter(|p| members.contains(&p.pid)) {
+        cpu_usage += process.cpu_usage;
+        memory += process.memory;
+        // The main pid is the first process whose parent lives outside the unit.
+        let is_root = process.parent.is_none_or(|ppid| !members.contains(&ppid));
+        if is_root && main_pid.is_none_or(|pid| process.pid < pid) {
+            main_pid = Some(process.pid);
+        }
+    }
+
+    ServiceInfo {
+        name,
+        main_pid: main_pid.or_else(|| pids.first().copied()),
+        tasks: tasks.unwrap_or(pids.len()),
+        pids,
+        cpu_usage,
+        memory,
+    }
+}
+
+// Units may delegate to child cgroups, so walk the whole subtree.
+fn read_procs(dir: &Path, pids: &mut Vec<u32>) {
+    if let Ok(contents) = fs::read_to_string(dir.join("cgroup.procs")) {
+        pids.extend(contents.lines().filter_map(|line| line.trim().parse::<u32>().ok()));
+    }
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            if entry.path().is_dir() {
+                read_procs(&entry.path(), pids);
+            }
+        }
+    }
+}
+
+fn read_tasks(dir: &Path) -> Option<usize> {
+    fs::read_to_string(dir.join("pids.current")).ok()?.trim().parse().ok()
+}
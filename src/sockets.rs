@@ -0,0 +1,245 @@
+use std::fmt;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Protocol::Tcp => write!(f, "tcp"),
+            Protocol::Udp => write!(f, "udp"),
+        }
+    }
+}
+
+/// One row of `/proc/net/{tcp,tcp6,udp,udp6}`.
+#[derive(Debug, Clone)]
+pub struct Socket {
+    pub protocol: Protocol,
+    pub local: SocketAddr,
+    pub remote: SocketAddr,
+    pub state: &'static str,
+    /// Matches the `socket:[inode]` links under `/proc/<pid>/fd`
+    pub inode: u64,
+}
+
+impl fmt::Display for Socket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.protocol, self.local)?;
+        if !self.remote.ip().is_unspecified() {
+            write!(f, " -> {}", self.remote)?;
+        }
+        write!(f, " {}", self.state)
+    }
+}
+
+/// One row of `/proc/net/unix`.
+#[derive(Debug, Clone)]
+pub struct UnixSocket {
+    /// Bound path; abstract names start with `@`
+    pub path: Option<String>,
+    pub kind: &'static str,
+    pub state: &'static str,
+    pub inode: u64,
+}
+
+/// Every TCP and UDP socket on the machine. Tables that cannot be read, e.g.
+/// without IPv6 support, are skipped.
+pub fn read_all() -> Vec<Socket> {
+    [
+        ("/proc/net/tcp", Protocol::Tcp),
+        ("/proc/net/tcp6", Protocol::Tcp),
+        ("/proc/net/udp", Protocol::Udp),
+        ("/proc/net/udp6", Protocol::Udp),
+    ]
+    .into_iter()
+    .filter_map(|(path, protocol)| Some(parse_table(&fs::read_to_string(path).ok()?, protocol)))
+    .flatten()
+    .collect()
+}
+
+pub fn read_unix() -> Vec<UnixSocket> {
+    fs::read_to_string("/proc/net/unix").map(|contents| parse_unix(&contents)).unwrap_or_default()
+}
+
+fn parse_unix(contents: &str) -> Vec<UnixSocket> {
+    contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            // Num RefCount Protocol Flags Type St Inode [Path]
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let flags = u32::from_str_radix(fields.get(3)?, 16).ok()?;
+            let kind = match u16::from_str_radix(fields.get(4)?, 16).ok()? {
+                1 => "stream",
+                2 => "dgram",
+                5 => "seqpacket",
+                _ => "other",
+            };
+            // __SO_ACCEPTCON marks a listening socket
+            let state = if flags & 0x0001_0000 != 0 {
+                "LISTEN"
+            } else {
+                match *fields.get(5)? {
+                    "01" => "UNCONN",
+                    "02" => "CONNECTING",
+                    "03" => "CONNECTED",
+                    "04" => "DISCONNECTING",
+                    _ => "UNKNOWN",
+                }
+            };
+            Some(UnixSocket {
+                path: (fields.len() > 7).then(|| fields[7..].join(" ")),
+                kind,
+                state,
+                inode: fields.get(6)?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+fn parse_table(contents: &str, protocol: Protocol) -> Vec<Socket> {
+    contents
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let state = u8::from_str_radix(fields.get(3)?, 16).ok()?;
+            Some(Socket {
+                protocol,
+                local: parse_address(fields.get(1)?)?,
+                remote: parse_address(fields.get(2)?)?,
+                state: state_name(protocol, state),
+                inode: fields.get(9)?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Parses `0100007F:1F90`. The address is printed as native-endian 32-bit words
+/// (8 hex digits for IPv4, 32 for IPv6) holding network-order bytes; the port is plain hex.
+fn parse_address(field: &str) -> Option<SocketAddr> {
+    let (address, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let mut words = Vec::with_capacity(4);
+    for i in (0..address.len()).step_by(8) {
+        let word = u32::from_str_radix(address.get(i..i + 8)?, 16).ok()?;
+        words.push(word.to_ne_bytes());
+    }
+    let ip = match words.as_slice() {
+        [a] => IpAddr::V4(Ipv4Addr::from(*a)),
+        [a, b, c, d] => {
+            let mut bytes = [0; 16];
+            for (chunk, word) in bytes.chunks_mut(4).zip([a, b, c, d]) {
+                chunk.copy_from_slice(word);
+            }
+            IpAddr::V6(Ipv6Addr::from(bytes))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+/// Names from `include/net/tcp_states.h`. UDP reuses them: a connected UDP
+/// socket is "established" and an unconnected one "close", shown as CONNECTED
+/// and UNCONN, the names the connections table also gives unix sockets.
+fn state_name(protocol: Protocol, state: u8) -> &'static str {
+    match (protocol, state) {
+        (Protocol::Udp, 0x01) => "CONNECTED",
+        (Protocol::Udp, _) => "UNCONN",
+        (_, 0x01) => "ESTABLISHED",
+        (_, 0x02) => "SYN_SENT",
+        (_, 0x03) => "SYN_RECV",
+        (_, 0x04) => "FIN_WAIT1",
+        (_, 0x05) => "FIN_WAIT2",
+        (_, 0x06) => "TIME_WAIT",
+        (_, 0x07) => "CLOSE",
+        (_, 0x08) => "CLOSE_WAIT",
+        (_, 0x09) => "LAST_ACK",
+        (_, 0x0A) => "LISTEN",
+        (_, 0x0B) => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+// The fixtures hold addresses as a little-endian kernel prints them.
+#[cfg(all(test, target_endian = "little"))]
+mod tests {
+    use super::*;
+
+    const TCP: &str = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 12345 1 0000000000000000 100 0 0 10 0
+   1: 0F02000A:C350 22D8B85D:01BB 01 00000000:00000000 02:000A7A5B 00000000  1000        0 12346 2 0000000000000000 20 4 30 10 -1
+";
+
+    const TCP6: &str = "\
+  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000001000000:0016 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 23456 1 0000000000000000 100 0 0 10 0
+";
+
+    const UDP: &str = "\
+   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode ref pointer drops
+  100: 00000000:0044 00000000:0000 07 00000000:00000000 00:00000000 00000000     0        0 34567 2 0000000000000000 0
+  200: 0F02000A:D431 08080808:0035 01 00000000:00000000 00:00000000 00000000  1000        0 34568 2 0000000000000000 0
+";
+
+    const UNIX: &str = "\
+Num       RefCount Protocol Flags    Type St Inode Path
+0000000000000000: 00000002 00000000 00010000 0001 01 45678 /run/systemd/notify
+0000000000000000: 00000003 00000000 00000000 0001 03 45679
+0000000000000000: 00000002 00000000 00000000 0002 01 45680 @/tmp/.X11-unix/X0
+";
+
+    #[test]
+    fn parses_tcp_table() {
+        let sockets = parse_table(TCP, Protocol::Tcp);
+        assert_eq!(sockets.len(), 2);
+        assert_eq!(sockets[0].local, "127.0.0.1:8080".parse().unwrap());
+        assert_eq!(sockets[0].state, "LISTEN");
+        assert_eq!(sockets[0].inode, 12345);
+        assert_eq!(sockets[1].local, "10.0.2.15:50000".parse().unwrap());
+        assert_eq!(sockets[1].remote, "93.184.216.34:443".parse().unwrap());
+        assert_eq!(sockets[1].state, "ESTABLISHED");
+    }
+
+    #[test]
+    fn parses_tcp6_table() {
+        let sockets = parse_table(TCP6, Protocol::Tcp);
+        assert_eq!(sockets.len(), 1);
+        assert_eq!(sockets[0].local, "[::1]:22".parse().unwrap());
+        assert!(sockets[0].remote.ip().is_unspecified());
+        assert_eq!(sockets[0].state, "LISTEN");
+    }
+
+    #[test]
+    fn parses_udp_table() {
+        let sockets = parse_table(UDP, Protocol::Udp);
+        assert_eq!(sockets.len(), 2);
+        assert_eq!(sockets[0].local, "0.0.0.0:68".parse().unwrap());
+        assert_eq!(sockets[0].state, "UNCONN");
+        assert_eq!(sockets[0].inode, 34567);
+        assert_eq!(sockets[1].remote, "8.8.8.8:53".parse().unwrap());
+        assert_eq!(sockets[1].state, "CONNECTED");
+    }
+
+    #[test]
+    fn parses_unix_table() {
+        let sockets = parse_unix(UNIX);
+        let rows: Vec<_> = sockets.iter().map(|s| (s.path.as_deref(), s.kind, s.state, s.inode)).collect();
+        assert_eq!(
+            rows,
+            [
+                (Some("/run/systemd/notify"), "stream", "LISTEN", 45678),
+                (None, "stream", "CONNECTED", 45679),
+                (Some("@/tmp/.X11-unix/X0"), "dgram", "UNCONN", 45680),
+            ]
+        );
+    }
+}
@@ -30,16 +30,13 @@ pub fn format_uptime(uptime_seconds: u64) -> String {
     }
 }
 
-pub fn get_cpu_temperature() -> Option<f32> {
-    // This is a placeholder - actual temperature reading would require
-    // platform-specific implementations or additional crates
-    None
-}
-
-pub fn get_load_average() -> Option<(f32, f32, f32)> {
-    // This is a placeholder - load average is Unix-specific
-    // On Windows, we could calculate an equivalent based on CPU usage
-    None
+pub fn get_load_average() -> Option<(f64, f64, f64)> {
+    // Load average is Unix-specific; sysinfo reports zeros on Windows
+    if cfg!(windows) {
+        return None;
+    }
+    let load = System::load_average();
+    Some((load.one, load.five, load.fifteen))
 }
 
 pub fn get_process_count(system: &System) -> usize {
@@ -0,0 +1,141 @@
+use anyhow::{anyhow, Context, Result};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::crypto::{ring, CryptoProvider};
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore, ServerConfig};
+use tokio_rustls::{TlsAcceptor, TlsConnector};
+
+use crate::config::{AgentTlsConfig, ClientTlsConfig};
+
+/// A connection to or from an agent, with or without TLS.
+pub trait Stream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> Stream for T {}
+
+pub type BoxStream = Box<dyn Stream>;
+
+/// Wraps accepted agent connections in TLS when the agent is configured for it.
+#[derive(Clone)]
+pub struct Acceptor(Option<TlsAcceptor>);
+
+impl Acceptor {
+    pub fn new(tls: Option<&AgentTlsConfig>) -> Result<Self> {
+        let Some(tls) = tls else {
+            return Ok(Self(None));
+        };
+
+        let builder = ServerConfig::builder_with_provider(provider()).with_safe_default_protocol_versions()?;
+        let builder = match &tls.client_ca {
+            Some(ca) => {
+                let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(load_roots(ca)?), provider())
+                    .build()
+                    .context("invalid client CA")?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+        let config = builder
+            .with_single_cert(load_certs(&tls.cert)?, load_key(&tls.key)?)
+            .context("invalid agent certificate or key")?;
+
+        Ok(Self(Some(TlsAcceptor::from(Arc::new(config)))))
+    }
+
+    pub fn is_tls(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub async fn accept(&self, stream: TcpStream) -> Result<BoxStream> {
+        stream.set_nodelay(true)?;
+        match &self.0 {
+            Some(acceptor) => Ok(Box::new(acceptor.accept(stream).await?)),
+            None => Ok(Box::new(stream)),
+        }
+    }
+}
+
+/// Opens client connections to agents, in TLS when the client is configured for it.
+#[derive(Clone)]
+pub struct Connector {
+    tls: Option<(TlsConnector, Option<String>)>, // (connector, server name override)
+}
+
+impl Connector {
+    pub fn new(tls: Option<&ClientTlsConfig>) -> Result<Self> {
+        let Some(tls) = tls else {
+            return Ok(Self { tls: None });
+        };
+
+        let builder = ClientConfig::builder_with_provider(provider())
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(load_roots(&tls.ca)?);
+        let config = match (&tls.cert, &tls.key) {
+            (Some(cert), Some(key)) => builder
+                .with_client_auth_cert(load_certs(cert)?, load_key(key)?)
+                .context("invalid client certificate or key")?,
+            (None, None) => builder.with_no_client_auth(),
+            _ => return Err(anyhow!("client TLS needs both `cert` and `key` for mutual TLS")),
+        };
+
+        Ok(Self {
+            tls: Some((TlsConnector::from(Arc::new(config)), tls.server_name.clone())),
+        })
+    }
+
+    pub async fn connect(&self, address: &str) -> Result<BoxStream> {
+        let stream = TcpStream::connect(address).await?;
+        stream.set_nodelay(true)?;
+        let Some((connector, server_name)) = &self.tls else {
+            return Ok(Box::new(stream));
+        };
+
+        let name = match server_name {
+            Some(name) => name.clone(),
+            None => host_part(address).to_string(),
+        };
+        let name = ServerName::try_from(name).context("invalid TLS server name")?;
+        Ok(Box::new(connector.connect(name, stream).await?))
+    }
+}
+
+// Strips the port, and the brackets around IPv6 literals, from `host:port`.
+fn host_part(address: &str) -> &str {
+    let host = address.rsplit_once(':').map_or(address, |(host, _)| host);
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+fn provider() -> Arc<CryptoProvider> {
+    Arc::new(ring::default_provider())
+}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("invalid PEM in {}", path.display()))?;
+    if certs.is_empty() {
+        return Err(anyhow!("no certificates found in {}", path.display()));
+    }
+    Ok(certs)
+}
+
+fn load_key(path: &Path) -> Result<PrivateKeyDer<'static>> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    rustls_pemfile::private_key(&mut BufReader::new(file))
+        .with_context(|| format!("invalid PEM in {}", path.display()))?
+        .ok_or_else(|| anyhow!("no private key found in {}", path.display()))
+}
+
+fn load_roots(path: &Path) -> Result<RootCertStore> {
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(path)? {
+        roots.add(cert).with_context(|| format!("invalid CA certificate in {}", path.display()))?;
+    }
+    Ok(roots)
+}
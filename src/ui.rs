@@ -1,103 +1,437 @@
-use crate::app::{App, SystemInfo};
+use crate::app::{
+    App, Dialog, ProcessInfo, SortBy, SystemInfo, TableView, CPU_TAB, CUSTOM_TAB, DISKS_TAB, LOGS_TAB, NETWORK_TAB, SENSORS_TAB, HOSTS_TAB, MEMORY_TAB, OVERVIEW_TAB, PROCESSES_TAB, SERVICES_TAB,
+};
+use crate::bandwidth::Method;
+use crate::dashboard::{Dashboard, Widget};
+use crate::fds::{FdKind, OpenFile};
+use crate::interfaces::Interface;
+use crate::logs::Severity;
+use crate::plugins::{CustomMetric, Display};
+use crate::power::PowerStatus;
+use crate::process_control::{format_cpu_list, IoClass};
+use crate::process_events::EventKind;
+use crate::process_status::ProcessStatus;
+use crate::procstat::{CpuBreakdown, CpuStats};
+use crate::remote::ConnectionStatus;
+use crate::sensors::{Level, Sensor};
 use crate::system_info::{format_bytes, format_uptime};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{
-        Block, Borders, Cell, Gauge, Paragraph, Row,
-        Sparkline, Table, Tabs, Wrap,
+        Block, Borders, Cell, Clear, Gauge, Paragraph, Row, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Sparkline, Table, TableState, Tabs, Wrap,
     },
     Frame,
 };
 
-const TABS: &[&str] = &["Overview", "Processes", "Network", "Disks"];
+pub const TABS: &[&str] = &["Overview", "Processes", "Network", "Disks", "Services", "Hosts", "Memory", "CPU", "Custom", "Logs", "Sensors"];
+/// `TABS` as shown in the compact profile, where the full titles need more than 80 columns.
+const SHORT_TABS: &[&str] = &["Ovw", "Proc", "Net", "Disk", "Svc", "Host", "Mem", "CPU", "Cust", "Logs", "Sens"];
+
+/// Screen areas of the last frame that respond to the mouse.
+#[derive(Debug, Clone, Default)]
+pub struct HitAreas {
+    pub tabs: Vec<(Rect, usize)>,
+    /// Body of the current tab's selectable table, and the list index of its first row
+    pub rows: Option<(Rect, usize)>,
+    pub sort_columns: Vec<(Rect, SortBy)>,
+}
+
+impl HitAreas {
+    pub fn tab_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.tabs.iter().find(|(rect, _)| contains(*rect, column, row)).map(|(_, tab)| *tab)
+    }
+
+    pub fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        let (body, first_row) = self.rows?;
+        contains(body, column, row).then(|| first_row + (row - body.y) as usize)
+    }
+
+    pub fn sort_column_at(&self, column: u16, row: u16) -> Option<SortBy> {
+        self.sort_columns
+            .iter()
+            .find(|(rect, _)| contains(*rect, column, row))
+            .map(|(_, sort)| sort.clone())
+    }
+}
+
+fn contains(rect: Rect, column: u16, row: u16) -> bool {
+    column >= rect.x && column < rect.right() && row >= rect.y && row < rect.bottom()
+}
+
+/// Below this size nothing is drawn but a request to enlarge the terminal.
+const MIN_WIDTH: u16 = 50;
+const MIN_HEIGHT: u16 = 16;
+
+/// How much fits on screen, chosen from the terminal size on every frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Profile {
+    /// Around 80x24 or a tmux split: secondary panels are hidden or condensed
+    Compact,
+    Normal,
+    /// Wide monitors: charts sit beside the process list
+    Wide,
+}
+
+impl Profile {
+    fn for_area(area: Rect) -> Self {
+        if area.width < 100 || area.height < 30 {
+            Profile::Compact
+        } else if area.width >= 180 {
+            Profile::Wide
+        } else {
+            Profile::Normal
+        }
+    }
+}
+
+/// Draws a frame and returns where its clickable elements ended up.
+pub fn render(f: &mut Frame, app: &App) -> HitAreas {
+    let mut hits = HitAreas::default();
+    let size = f.size();
+    if size.width < MIN_WIDTH || size.height < MIN_HEIGHT {
+        render_too_small(f, size);
+        return hits;
+    }
 
-pub fn render(f: &mut Frame, app: &App) {
+    let profile = Profile::for_area(size);
+    let footer = footer_lines(profile, size.width);
+    let footer_height = match profile {
+        Profile::Compact => footer.len() as u16,
+        _ => footer.len() as u16 + 2,
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
-        .split(f.size());
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(footer_height)])
+        .split(size);
 
     // Header
-    render_header(f, chunks[0], app);
+    render_header(f, chunks[0], app, &mut hits, profile);
 
     // Main content
     match app.current_tab {
-        0 => render_overview(f, chunks[1], app),
-        1 => render_processes(f, chunks[1], app),
-        2 => render_network(f, chunks[1], app),
-        3 => render_disks(f, chunks[1], app),
-        _ => render_overview(f, chunks[1], app),
+        OVERVIEW_TAB => render_overview(f, chunks[1], app, profile),
+        PROCESSES_TAB => render_processes(f, chunks[1], app, &mut hits, profile),
+        NETWORK_TAB => render_network(f, chunks[1], app, &mut hits, profile),
+        DISKS_TAB => render_disks(f, chunks[1], app),
+        SERVICES_TAB => render_services(f, chunks[1], app, &mut hits),
+        HOSTS_TAB => render_hosts(f, chunks[1], app, &mut hits),
+        MEMORY_TAB => render_memory(f, chunks[1], app),
+        CPU_TAB => render_cpu(f, chunks[1], app),
+        CUSTOM_TAB => render_custom(f, chunks[1], app),
+        LOGS_TAB => render_logs(f, chunks[1], app),
+        SENSORS_TAB => render_sensors(f, chunks[1], app, &mut hits),
+        _ => match app.current_dashboard() {
+            Some(dashboard) => render_dashboard(f, chunks[1], app, dashboard),
+            None => render_overview(f, chunks[1], app, profile),
+        },
     }
 
     // Footer
-    render_footer(f, chunks[2]);
+    render_footer(f, chunks[2], footer, profile);
+
+    if let Some(dialog) = &app.dialog {
+        render_dialog(f, dialog);
+        // The dialog is modal, so nothing behind it is clickable
+        hits = HitAreas::default();
+    }
+    hits
+}
+
+fn render_too_small(f: &mut Frame, area: Rect) {
+    let message = Paragraph::new(vec![
+        Line::from(Span::styled("Terminal too small", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))),
+        Line::from(format!("{}x{}, need at least {}x{}", area.width, area.height, MIN_WIDTH, MIN_HEIGHT)),
+    ])
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+    let height = area.height.min(2);
+    f.render_widget(message, Rect { y: area.y + area.height.saturating_sub(height) / 2, height, ..area });
+}
+
+/// The rows below the header of a bordered table, for hit-testing.
+fn table_body(area: Rect) -> Rect {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    Rect {
+        y: inner.y.saturating_add(1),
+        height: inner.height.saturating_sub(1),
+        ..inner
+    }
+}
+
+/// Renders a bordered table scrolled to keep `selected` in view, with a scrollbar
+/// on the right border when the rows overflow. Returns the body and first visible row.
+fn render_scrolling_table(
+    f: &mut Frame,
+    area: Rect,
+    table: Table,
+    rows: usize,
+    selected: Option<usize>,
+    view: &TableView,
+) -> (Rect, usize) {
+    let body = table_body(area);
+    let mut state = TableState::default().with_selected(selected).with_offset(view.offset.get());
+    f.render_stateful_widget(table, area, &mut state);
+    view.offset.set(state.offset());
+    view.height.set(body.height as usize);
+
+    if rows > body.height as usize {
+        let mut scrollbar = ScrollbarState::new(rows).position(selected.unwrap_or(state.offset()));
+        f.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).begin_symbol(None).end_symbol(None),
+            Rect { y: body.y, height: body.height, ..area },
+            &mut scrollbar,
+        );
+    }
+    (body, state.offset())
 }
 
-fn render_header(f: &mut Frame, area: Rect, app: &App) {
-    let tabs = Tabs::new(TABS.to_vec())
-        .block(Block::default().borders(Borders::ALL).title("System Monitor"))
+fn render_header(f: &mut Frame, area: Rect, app: &App, hits: &mut HitAreas, profile: Profile) {
+    let mut title = vec![Span::raw("System Monitor")];
+    if let Some(host) = app.hosts.get(app.selected_host) {
+        let host = host.lock().unwrap();
+        title.push(Span::raw(format!(" - {}", host.address)));
+        if host.is_stale() {
+            title.push(Span::styled(
+                " [STALE DATA]",
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+    }
+
+    if !app.alerts.is_empty() {
+        let names: Vec<String> = app
+            .alerts
+            .iter()
+            .map(|alert| format!("{} {:.1} > {}", alert.name, alert.value, alert.threshold))
+            .collect();
+        title.push(Span::styled(
+            format!(" [ALERT: {}]", names.join(", ")),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    // Watch notifications stay in the header for half a minute
+    if let Some(notification) = app.watch.notifications.back() {
+        if chrono::Local::now() - notification.time < chrono::Duration::seconds(30) {
+            title.push(Span::styled(
+                format!(" [WATCH: {} at {}]", notification.message, notification.time.format("%H:%M:%S")),
+                Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+            ));
+        }
+    }
+
+    let builtin = if profile == Profile::Compact { SHORT_TABS } else { TABS };
+    let titles: Vec<&str> = builtin.iter().copied().chain(app.dashboards.iter().map(|d| d.name.as_str())).collect();
+
+    // Tabs that do not fit are scrolled off to the left, keeping the selected one in view.
+    // Each title takes one space either side, then a one-cell divider.
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let selected = app.current_tab.min(titles.len() - 1);
+    let fits = |first: usize| {
+        titles[first..=selected].iter().map(|title| title.len() as u16 + 3).sum::<u16>() <= inner.width + 1
+    };
+    let first = (0..selected).find(|&first| fits(first)).unwrap_or(selected);
+
+    let tabs = Tabs::new(titles[first..].to_vec())
+        .block(Block::default().borders(Borders::ALL).title(Line::from(title)))
         .style(Style::default().fg(Color::White))
         .highlight_style(
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
         )
-        .select(app.current_tab);
+        .select(app.current_tab.saturating_sub(first));
     f.render_widget(tabs, area);
+
+    // Mirrors how `Tabs` lays out titles
+    let mut x = inner.x;
+    for (tab, title) in titles.iter().enumerate().skip(first) {
+        let width = (title.len() as u16 + 2).min(inner.right().saturating_sub(x));
+        if width > 0 {
+            hits.tabs.push((Rect { x, y: inner.y, width, height: 1 }, tab));
+        }
+        x = x.saturating_add(width + 1);
+    }
 }
 
-fn render_footer(f: &mut Frame, area: Rect) {
-    let help_text = vec![
-        Line::from(vec![
-            Span::styled("q", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(": Quit | "),
-            Span::styled("h/l", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(": Switch tabs | "),
-            Span::styled("j/k", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(": Navigate | "),
-            Span::styled("r", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(": Refresh | "),
-            Span::styled("s", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
-            Span::raw(": Sort | "),
-            Span::styled("Del", Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)),
-            Span::raw(": Kill Process"),
-        ]),
-    ];
-    
-    let help = Paragraph::new(help_text)
-        .block(Block::default().borders(Borders::ALL).title("Help"))
+// (key, description, shown in compact mode)
+const HELP: &[(&str, &str, bool)] = &[
+    ("q", "Quit", true),
+    ("h/l", "Switch tabs", true),
+    ("j/k", "Navigate", true),
+    ("PgUp/PgDn", "Page", false),
+    ("r", "Refresh", false),
+    ("s", "Sort", true),
+    ("I", "CPU% Mode", false),
+    ("u", "Process/Unit", false),
+    ("n/o/a", "Nice/IO/Affinity", false),
+    ("z/c", "Stop/Continue", false),
+    ("w", "Watch", false),
+    ("f", "Open Files", false),
+    ("/", "Filter", false),
+    ("t", "Conn State", false),
+    ("v", "Virtual Ifaces", false),
+    ("x", "Problem States", false),
+    ("p/e", "Log Pause/Level", false),
+    ("Del", "Kill Process", true),
+];
+
+/// Packs the key help into as many lines as `width` needs; compact mode keeps
+/// only the essential keys, on a single borderless line.
+fn footer_lines(profile: Profile, width: u16) -> Vec<Line<'static>> {
+    let compact = profile == Profile::Compact;
+    let width = if compact { width } else { width.saturating_sub(2) } as usize;
+    let mut lines: Vec<Vec<Span<'static>>> = vec![Vec::new()];
+    let mut used = 0;
+    for &(key, description, essential) in HELP {
+        if compact && !essential {
+            continue;
+        }
+        let item_width = key.chars().count() + description.chars().count() + 2;
+        let line = lines.last_mut().expect("lines starts non-empty");
+        if !line.is_empty() {
+            if used + 3 + item_width > width {
+                lines.push(Vec::new());
+                used = 0;
+            } else {
+                line.push(Span::raw(" | "));
+                used += 3;
+            }
+        }
+        let color = if key == "Del" { Color::Red } else { Color::Yellow };
+        let line = lines.last_mut().expect("lines starts non-empty");
+        line.push(Span::styled(key, Style::default().fg(color).add_modifier(Modifier::BOLD)));
+        line.push(Span::raw(format!(": {}", description)));
+        used += item_width;
+    }
+    if compact {
+        lines.truncate(1);
+    }
+    lines.into_iter().map(Line::from).collect()
+}
+
+fn render_footer(f: &mut Frame, area: Rect, lines: Vec<Line<'static>>, profile: Profile) {
+    let mut help = Paragraph::new(lines)
         .style(Style::default().fg(Color::White))
-        .alignment(Alignment::Center)
-        .wrap(Wrap { trim: true });
+        .alignment(Alignment::Center);
+    if profile != Profile::Compact {
+        help = help.block(Block::default().borders(Borders::ALL).title("Help"));
+    }
     f.render_widget(help, area);
 }
 
-fn render_overview(f: &mut Frame, area: Rect, app: &App) {
+fn render_overview(f: &mut Frame, area: Rect, app: &App, profile: Profile) {
     let system_info = app.get_system_info();
     
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(8),
+            Constraint::Length(if system_info.container.is_some() { 9 } else { 8 }),
             Constraint::Length(6),
-            Constraint::Min(8),
+            // Only machines with a battery get the battery row
+            Constraint::Length(if app.power.is_some() { 6 } else { 0 }),
+            Constraint::Min(0),
         ])
         .split(area);
 
-    // System Information
-    render_system_info(f, chunks[0], &system_info);
+    // The pressure table needs about 70 columns, so compact mode drops it
+    if profile == Profile::Compact {
+        render_system_info(f, chunks[0], &system_info);
+    } else {
+        let top = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[0]);
+
+        // System Information
+        render_system_info(f, top[0], &system_info);
+
+        // Pressure Stall Information
+        render_pressure(f, top[1], app);
+    }
     
     // Resource Usage
     render_resource_usage(f, chunks[1], app, &system_info);
+
+    if let Some(power) = &app.power {
+        render_battery(f, chunks[2], app, power);
+    }
     
-    // Charts
-    render_charts(f, chunks[2], app);
+    // Charts, when there is room for more than their borders
+    if chunks[3].height >= 5 {
+        render_charts(f, chunks[3], app, Direction::Horizontal);
+    }
+}
+
+fn render_battery(f: &mut Frame, area: Rect, app: &App, power: &PowerStatus) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD));
+    let mut lines = Vec::new();
+    for battery in &power.batteries {
+        let color = if battery.percent < 10.0 {
+            Color::Red
+        } else if battery.percent < 25.0 {
+            Color::Yellow
+        } else {
+            Color::Green
+        };
+        lines.push(Line::from(vec![
+            label("Charge: "),
+            Span::styled(format!("{:.0}%", battery.percent), Style::default().fg(color)),
+            Span::raw(format!(" {} ({})", battery.status, battery.name)),
+        ]));
+        let mut details = vec![
+            label("Rate: "),
+            Span::raw(battery.rate.map_or("-".to_string(), |rate| format!("{:.1} W", rate))),
+        ];
+        if let Some(seconds) = battery.time_remaining {
+            let until = if battery.status == "Charging" { "until full" } else { "left" };
+            details.push(label(" | Time: "));
+            details.push(Span::raw(format!("{}h {:02}m {}", seconds / 3600, seconds % 3600 / 60, until)));
+        }
+        if let Some(health) = battery.health {
+            details.push(label(" | Health: "));
+            details.push(Span::raw(format!("{:.0}%", health)));
+        }
+        lines.push(Line::from(details));
+    }
+
+    let title = match power.ac_online {
+        Some(true) => "Battery (AC connected)",
+        Some(false) => "Battery (on battery)",
+        None => "Battery",
+    };
+    let battery = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    f.render_widget(battery, chunks[0]);
+
+    let history: Vec<u64> = app.battery_history.iter().map(|&x| x as u64).collect();
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .title("Charge History (%)")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .data(&history)
+        .max(100)
+        .style(Style::default().fg(Color::Yellow));
+    f.render_widget(sparkline, chunks[1]);
 }
 
 fn render_system_info(f: &mut Frame, area: Rect, system_info: &SystemInfo) {
-    let info_text = vec![
+    let mut info_text = vec![
         Line::from(vec![
             Span::styled("System: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
             Span::raw(&system_info.system_name),
@@ -124,6 +458,23 @@ fn render_system_info(f: &mut Frame, area: Rect, system_info: &SystemInfo) {
         ]),
     ];
 
+    if let Some(container) = &system_info.container {
+        let memory = format!(
+            "{} of {}",
+            container.memory_limit.map_or("unlimited".to_string(), format_bytes),
+            format_bytes(container.host_total_memory)
+        );
+        let cpus = format!(
+            "{} of {} CPUs",
+            container.cpu_limit.map_or("unlimited".to_string(), |c| format!("{:.2}", c)),
+            container.host_cpu_count
+        );
+        info_text.push(Line::from(vec![
+            Span::styled("Container: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{} memory, {}", memory, cpus)),
+        ]));
+    }
+
     let system_block = Paragraph::new(info_text)
         .block(
             Block::default()
@@ -141,7 +492,11 @@ fn render_resource_usage(f: &mut Frame, area: Rect, app: &App, system_info: &Sys
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
         .split(area);
 
-    // CPU Usage
+    render_cpu_gauge(f, chunks[0], app);
+    render_memory_gauge(f, chunks[1], system_info);
+}
+
+fn render_cpu_gauge(f: &mut Frame, area: Rect, app: &App) {
     let cpu_usage = if let Some(&last_cpu) = app.cpu_history.last() {
         last_cpu as u16
     } else {
@@ -159,12 +514,17 @@ fn render_resource_usage(f: &mut Frame, area: Rect, app: &App, system_info: &Sys
             Style::default()
                 .fg(if cpu_usage > 80 { Color::Red } else if cpu_usage > 60 { Color::Yellow } else { Color::Green })
         )
-        .percent(cpu_usage)
+        .percent(cpu_usage.min(100))
         .label(format!("{}%", cpu_usage));
-    f.render_widget(cpu_gauge, chunks[0]);
+    f.render_widget(cpu_gauge, area);
+}
 
-    // Memory Usage
-    let memory_usage = ((system_info.used_memory as f64 / system_info.total_memory as f64) * 100.0) as u16;
+fn render_memory_gauge(f: &mut Frame, area: Rect, system_info: &SystemInfo) {
+    let memory_usage = if system_info.total_memory > 0 {
+        ((system_info.used_memory as f64 / system_info.total_memory as f64) * 100.0).min(100.0) as u16
+    } else {
+        0
+    };
     
     let memory_gauge = Gauge::default()
         .block(
@@ -181,16 +541,28 @@ fn render_resource_usage(f: &mut Frame, area: Rect, app: &App, system_info: &Sys
         )
         .percent(memory_usage)
         .label(format!("{}%", memory_usage));
-    f.render_widget(memory_gauge, chunks[1]);
+    f.render_widget(memory_gauge, area);
 }
 
-fn render_charts(f: &mut Frame, area: Rect, app: &App) {
+fn render_charts(f: &mut Frame, area: Rect, app: &App, direction: Direction) {
+    let constraints = if app.pressure.is_some() {
+        vec![Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)]
+    } else {
+        vec![Constraint::Percentage(50), Constraint::Percentage(50)]
+    };
     let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .direction(direction)
+        .constraints(constraints)
         .split(area);
 
-    // CPU History Sparkline
+    render_cpu_history(f, chunks[0], app);
+    render_memory_history(f, chunks[1], app);
+    if app.pressure.is_some() {
+        render_pressure_history(f, chunks[2], app);
+    }
+}
+
+fn render_cpu_history(f: &mut Frame, area: Rect, app: &App) {
     let cpu_data: Vec<u64> = app.cpu_history.iter().map(|&x| x as u64).collect();
     let cpu_sparkline = Sparkline::default()
         .block(
@@ -201,9 +573,10 @@ fn render_charts(f: &mut Frame, area: Rect, app: &App) {
         )
         .data(&cpu_data)
         .style(Style::default().fg(Color::Green));
-    f.render_widget(cpu_sparkline, chunks[0]);
+    f.render_widget(cpu_sparkline, area);
+}
 
-    // Memory History Sparkline
+fn render_memory_history(f: &mut Frame, area: Rect, app: &App) {
     let memory_data: Vec<u64> = app.memory_history.iter().map(|&x| x as u64).collect();
     let memory_sparkline = Sparkline::default()
         .block(
@@ -214,23 +587,169 @@ fn render_charts(f: &mut Frame, area: Rect, app: &App) {
         )
         .data(&memory_data)
         .style(Style::default().fg(Color::Magenta));
-    f.render_widget(memory_sparkline, chunks[1]);
+    f.render_widget(memory_sparkline, area);
+}
+
+// Worst of the cpu/memory/io "some" avg10 values at each sample
+fn render_pressure_history(f: &mut Frame, area: Rect, app: &App) {
+    let pressure_data: Vec<u64> = app
+        .pressure_history
+        .iter()
+        .map(|&(cpu, memory, io)| cpu.max(memory).max(io).ceil() as u64)
+        .collect();
+    let pressure_sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .title("Pressure History (max some avg10)")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        )
+        .data(&pressure_data)
+        .style(Style::default().fg(Color::Red));
+    f.render_widget(pressure_sparkline, area);
+}
+
+fn render_pressure(f: &mut Frame, area: Rect, app: &App) {
+    let block = Block::default()
+        .title("Pressure Stall (%)")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red));
+
+    let Some(stats) = &app.pressure else {
+        let unavailable = Paragraph::new("PSI is not available on this kernel (needs Linux 4.20+ with CONFIG_PSI)")
+            .block(block)
+            .style(Style::default().fg(Color::DarkGray))
+            .wrap(Wrap { trim: true });
+        f.render_widget(unavailable, area);
+        return;
+    };
+
+    let header_cells = ["", "some 10s", "60s", "300s", "full 10s", "60s", "300s"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells);
+
+    let rows = [("CPU", &stats.cpu), ("Memory", &stats.memory), ("IO", &stats.io)]
+        .into_iter()
+        .map(|(name, pressure)| {
+            let mut cells = vec![Cell::from(name).style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))];
+            let averages = [pressure.map(|p| p.some), pressure.and_then(|p| p.full)];
+            for averages in averages {
+                match averages {
+                    Some(a) => cells.extend([a.avg10, a.avg60, a.avg300].map(|value| {
+                        Cell::from(format!("{:.2}", value))
+                            .style(Style::default().fg(severity_color(severity(value, 10.0, 40.0))))
+                    })),
+                    None => cells.extend((0..3).map(|_| Cell::from("-"))),
+                }
+            }
+            Row::new(cells)
+        });
+
+    let table = Table::new(rows)
+        .header(header)
+        .block(block)
+        .widths(&[
+            Constraint::Length(7),
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Length(6),
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Length(6),
+        ]);
+    f.render_widget(table, area);
 }
 
-fn render_processes(f: &mut Frame, area: Rect, app: &App) {
+fn render_processes(f: &mut Frame, area: Rect, app: &App, hits: &mut HitAreas, profile: Profile) {
+    // Wide terminals get the history charts and event log beside the table
+    let (area, side) = if profile == Profile::Wide {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(64)])
+            .split(area);
+        (columns[0], Some(columns[1]))
+    } else {
+        (area, None)
+    };
+    let events_height = if profile == Profile::Normal { 8 } else { 0 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(watched_height(app, profile)),
+            Constraint::Min(0),
+            Constraint::Length(events_height),
+        ])
         .split(area);
 
+    let processes = app.visible_processes();
+
     // Process count and sort info
-    let process_info = Paragraph::new(format!(
-        "Total Processes: {} | Sort by: {:?} | Selected: {}/{}",
-        app.processes.len(),
-        app.sort_by,
-        app.selected_process + 1,
-        app.processes.len()
-    ))
+    let summary = if profile == Profile::Compact {
+        format!(
+            "{} processes | Sort: {:?} | CPU%: {:?} | {}/{}",
+            app.processes.len(),
+            app.sort_by,
+            app.cpu_mode,
+            app.selected_process + 1,
+            processes.len()
+        )
+    } else {
+        format!(
+            "Total Processes: {} | Sort by: {:?} | CPU%: {} | Selected: {}/{}",
+            app.processes.len(),
+            app.sort_by,
+            app.cpu_mode.describe(),
+            app.selected_process + 1,
+            processes.len()
+        )
+    };
+
+    // Zombie, D-state and stopped counts, highlighted while any are present
+    let counts = app.state_counts();
+    let unreaped = if counts.unreaped > 0 { format!(" ({} unreaped)", counts.unreaped) } else { String::new() };
+    let states = if profile == Profile::Compact {
+        let marker = if counts.unreaped > 0 { "!" } else { "" };
+        format!(" | Z:{}{} D:{} T:{}", counts.zombie, marker, counts.disk_sleep, counts.stopped)
+    } else {
+        format!(" | Zombie: {}{} | D-state: {} | Stopped: {}", counts.zombie, unreaped, counts.disk_sleep, counts.stopped)
+    };
+    let states_style = if counts.unreaped > 0 || counts.disk_sleep > 0 {
+        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+    } else if counts.zombie > 0 || counts.stopped > 0 {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+
+    let mut details = String::new();
+    if profile != Profile::Compact {
+        if let Some(net) = app.selected_process_info().and_then(|p| p.net) {
+            details.push_str(&format!(
+                " | Net: ↓{}/s ↑{}/s ({})",
+                format_bytes(net.received),
+                format_bytes(net.transmitted),
+                net.method.describe()
+            ));
+        }
+    }
+    if let Some(host) = app.hosts.get(app.selected_host) {
+        let host = host.lock().unwrap();
+        if !host.allow_control {
+            details.push_str(" | Remote control disabled");
+        }
+        if let Some(action) = &host.last_action {
+            details.push_str(&format!(" | {}", action));
+        }
+    } else if let Some(message) = &app.status_message {
+        details.push_str(&format!(" | {}", message));
+    }
+    let process_info = Paragraph::new(Line::from(vec![
+        Span::raw(summary),
+        Span::styled(states, states_style),
+        Span::raw(details),
+    ]))
     .block(
         Block::default()
             .title("Process Information")
@@ -240,23 +759,75 @@ fn render_processes(f: &mut Frame, area: Rect, app: &App) {
     .style(Style::default().fg(Color::White));
     f.render_widget(process_info, chunks[0]);
 
-    // Process table
-    let header_cells = ["PID", "Name", "CPU%", "Memory", "Status"]
-        .iter()
-        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    // Process table; the last field says whether a column survives compact mode
+    let columns = [
+        ("PID", Some(SortBy::Pid), Constraint::Length(8), true),
+        ("Name", Some(SortBy::Name), Constraint::Min(20), true),
+        ("CPU%", Some(SortBy::Cpu), Constraint::Length(8), true),
+        ("Memory", Some(SortBy::Memory), Constraint::Length(12), true),
+        ("FDs", Some(SortBy::Fds), Constraint::Length(6), false),
+        ("Net/s", Some(SortBy::Net), Constraint::Length(10), false),
+        ("Nice", None, Constraint::Length(5), false),
+        ("IO", None, Constraint::Length(6), false),
+        ("Affinity", None, Constraint::Length(10), false),
+        ("Status", None, Constraint::Min(10), true),
+    ];
+    let shown: Vec<bool> = columns.iter().map(|column| profile != Profile::Compact || column.3).collect();
+    let columns: Vec<_> = columns.iter().zip(&shown).filter(|(_, &shown)| shown).map(|(column, _)| column).collect();
+    // Resolve the widths up front so the header cells can be hit-tested; fixed
+    // lengths make `Table` place the columns exactly here
+    let column_areas = table_columns(chunks[2], &columns.iter().map(|(_, _, width, _)| *width).collect::<Vec<_>>());
+    let widths: Vec<Constraint> = column_areas.iter().map(|rect| Constraint::Length(rect.width)).collect();
+    let header_cells = columns.iter().map(|(title, sort, _, _)| {
+        let title = match sort {
+            Some(sort) if *sort == app.sort_by => format!("{} {}", title, if app.sort_ascending { "▲" } else { "▼" }),
+            _ => title.to_string(),
+        };
+        Cell::from(title).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+    });
     let header = Row::new(header_cells).style(Style::default().bg(Color::Blue));
+    for ((_, sort, _, _), rect) in columns.iter().zip(&column_areas) {
+        if let Some(sort) = sort {
+            hits.sort_columns.push((*rect, sort.clone()));
+        }
+    }
 
-    let rows = app.processes.iter().enumerate().map(|(i, process)| {
+    let rows = processes.iter().enumerate().map(|(i, process)| {
         let cells = vec![
             Cell::from(process.pid.to_string()),
             Cell::from(process.name.clone()),
-            Cell::from(format!("{:.1}%", process.cpu_usage)),
+            Cell::from(format!("{:.1}%", app.process_cpu(process.cpu_usage))),
             Cell::from(format_bytes(process.memory)),
-            Cell::from(process.status.clone()),
+            Cell::from(process.fds.map_or("-".to_string(), |fds| fds.to_string())),
+            // A leading ~ marks rates guessed from connection counts
+            Cell::from(process.net.map_or("-".to_string(), |net| match net.method {
+                Method::Namespace { .. } => format_bytes(net.total()),
+                Method::Connections { .. } => format!("~{}", format_bytes(net.total())),
+            })),
+            Cell::from(process.nice.map_or("-".to_string(), |nice| nice.to_string())),
+            Cell::from(process.io_priority.map_or("-".to_string(), |p| p.label())),
+            Cell::from(process.affinity.as_ref().map_or("-".to_string(), |cores| {
+                if cores.len() >= app.cpu_count() {
+                    "all".to_string()
+                } else {
+                    format_cpu_list(cores)
+                }
+            })),
+            Cell::from(match app.zombies.unreaped_parent(process, &app.processes) {
+                Some(parent) => format!("{} (unreaped by {} {})", process.status, parent.name, parent.pid),
+                None => process.status.to_string(),
+            }),
         ];
+        let cells = cells.into_iter().zip(&shown).filter(|(_, &shown)| shown).map(|(cell, _)| cell);
         
         let style = if i == app.selected_process {
             Style::default().bg(Color::Yellow).fg(Color::Black)
+        } else if matches!(process.status, ProcessStatus::Zombie | ProcessStatus::DiskSleep) {
+            Style::default().fg(Color::Red)
+        } else if process.status == ProcessStatus::Stopped {
+            Style::default().fg(Color::DarkGray)
+        } else if app.watch.is_watched(&process.name) {
+            Style::default().fg(Color::Cyan)
         } else {
             Style::default().fg(Color::White)
         };
@@ -269,25 +840,293 @@ fn render_processes(f: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Processes")
+                .title(if app.problem_filter { "Processes (zombie, D-state and stopped only)" } else { "Processes" })
                 .border_style(Style::default().fg(Color::Green)),
         )
+        .widths(&widths);
+
+    hits.rows = Some(render_scrolling_table(
+        f,
+        chunks[2],
+        table,
+        processes.len(),
+        Some(app.selected_process),
+        &app.process_view,
+    ));
+
+    if !app.watch.processes.is_empty() {
+        render_watched(f, chunks[1], app);
+    }
+    if events_height > 0 {
+        render_process_events(f, chunks[3], app);
+    }
+    if let Some(side) = side {
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(side);
+        render_charts(f, parts[0], app, Direction::Vertical);
+        render_process_events(f, parts[1], app);
+    }
+}
+
+/// Splits the header row of a bordered table into its columns, spaced one cell apart like `Table`.
+fn table_columns(area: Rect, widths: &[Constraint]) -> Vec<Rect> {
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let header = Rect { height: inner.height.min(1), ..inner };
+    let mut constraints = Vec::with_capacity(widths.len() * 2);
+    for width in widths {
+        constraints.push(*width);
+        constraints.push(Constraint::Length(1));
+    }
+    constraints.pop();
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(constraints)
+        .split(header)
+        .iter()
+        .step_by(2)
+        .copied()
+        .collect()
+}
+
+fn watched_height(app: &App, profile: Profile) -> u16 {
+    let max_rows = if profile == Profile::Compact { 2 } else { 6 };
+    match app.watch.processes.len() {
+        0 => 0,
+        count => count.min(max_rows) as u16 + 3, // borders and header
+    }
+}
+
+fn render_watched(f: &mut Frame, area: Rect, app: &App) {
+    let header_cells = ["Name", "PID", "State", "Restarts", "CPU%", "CPU History", "Memory"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells).style(Style::default().bg(Color::Blue));
+
+    let rows = app.watch.processes.iter().map(|process| {
+        let (state, style) = match process.exited {
+            Some(time) => (format!("exited {}", time.format("%H:%M:%S")), Style::default().fg(Color::Red)),
+            None => ("running".to_string(), Style::default().fg(Color::Cyan)),
+        };
+        let cpu: Vec<f32> = process.cpu_history.iter().map(|&cpu| app.process_cpu(cpu)).collect();
+        Row::new(vec![
+            Cell::from(process.name.clone()),
+            Cell::from(process.pid.to_string()),
+            Cell::from(state),
+            Cell::from(process.restarts.to_string()),
+            Cell::from(cpu.last().map_or("-".to_string(), |cpu| format!("{:.1}%", cpu))),
+            Cell::from(text_sparkline(&cpu, 30)),
+            Cell::from(process.memory_history.last().map_or("-".to_string(), |&memory| format_bytes(memory))),
+        ])
+        .style(style)
+    });
+
+    let table = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Watched ({})", app.watch.patterns.join(", ")))
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
         .widths(&[
-            Constraint::Length(8),
             Constraint::Min(20),
             Constraint::Length(8),
+            Constraint::Length(16),
+            Constraint::Length(9),
+            Constraint::Length(8),
+            Constraint::Length(31),
             Constraint::Length(12),
-            Constraint::Min(10),
         ]);
+    f.render_widget(table, area);
+}
+
+fn render_sensors(f: &mut Frame, area: Rect, app: &App, hits: &mut HitAreas) {
+    if app.sensors.is_empty() {
+        render_placeholder(f, area, "Sensors", "No hwmon sensors or thermal zones found");
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(8), Constraint::Min(0)])
+        .split(area);
+
+    // History of the selected sensor, on a scale that puts critical at the top
+    let selected = &app.sensors[app.selected_sensor.min(app.sensors.len() - 1)];
+    let color = sensor_color(selected);
+    let scale = selected.critical.or(selected.max).unwrap_or(0.0).max(selected.history.iter().copied().fold(0.0, f64::max));
+    let data: Vec<u64> = selected.history.iter().map(|&value| (value * 10.0) as u64).collect();
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!("{} {} ({})", selected.chip, selected.label, format_sensor(selected.kind.unit(), selected.value)))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .data(&data)
+        .max((scale * 10.0).max(1.0) as u64)
+        .style(Style::default().fg(color));
+    f.render_widget(sparkline, chunks[0]);
+
+    let limit = |value: Option<f64>, unit: &str| value.map_or("-".to_string(), |value| format_sensor(unit, value));
+    let rows = app.sensors.iter().enumerate().map(|(i, sensor)| {
+        let unit = sensor.kind.unit();
+        let history: Vec<f32> = sensor.history.iter().map(|&value| value as f32).collect();
+        let row = Row::new(vec![
+            Cell::from(sensor.chip.clone()),
+            Cell::from(sensor.label.clone()),
+            Cell::from(format_sensor(unit, sensor.value)),
+            Cell::from(limit(sensor.max, unit)),
+            Cell::from(limit(sensor.critical, unit)),
+            Cell::from(text_sparkline(&history, 30)),
+        ]);
+        if i == app.selected_sensor {
+            row.style(Style::default().bg(Color::Yellow).fg(Color::Black))
+        } else {
+            row.style(Style::default().fg(sensor_color(sensor)))
+        }
+    });
+
+    let header_cells = ["Chip", "Sensor", "Value", "Max", "Critical", "History"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells).style(Style::default().bg(Color::Blue));
+
+    let table = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Sensors ({})", app.sensors.len()))
+                .border_style(Style::default().fg(Color::Green)),
+        )
+        .widths(&[
+            Constraint::Length(14),
+            Constraint::Length(20),
+            Constraint::Length(11),
+            Constraint::Length(11),
+            Constraint::Length(11),
+            Constraint::Percentage(100),
+        ]);
+
+    hits.rows = Some(render_scrolling_table(
+        f,
+        chunks[1],
+        table,
+        app.sensors.len(),
+        Some(app.selected_sensor),
+        &app.sensor_view,
+    ));
+}
+
+fn sensor_color(sensor: &Sensor) -> Color {
+    match sensor.level() {
+        Level::Critical => Color::Red,
+        Level::High => Color::Yellow,
+        Level::Normal => Color::Green,
+    }
+}
+
+fn format_sensor(unit: &str, value: f64) -> String {
+    match unit {
+        "RPM" => format!("{:.0} {}", value, unit),
+        "V" => format!("{:.3} {}", value, unit),
+        _ => format!("{:.1}{}", value, unit),
+    }
+}
 
-    f.render_widget(table, chunks[1]);
+/// The last `width` values as block characters, scaled to the largest of them.
+fn text_sparkline(values: &[f32], width: usize) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let values = &values[values.len().saturating_sub(width)..];
+    let max = values.iter().copied().fold(0.0f32, f32::max);
+    values
+        .iter()
+        .map(|&value| {
+            if max <= 0.0 {
+                BLOCKS[0]
+            } else {
+                BLOCKS[((value / max) * 7.0).round().clamp(0.0, 7.0) as usize]
+            }
+        })
+        .collect()
+}
+
+fn render_process_events(f: &mut Frame, area: Rect, app: &App) {
+    let mut title = format!("Process Events ({} recorded)", app.process_events.len());
+    if let Some(stats) = &app.cpu_stats {
+        title.push_str(&format!(" | forks: {:.1}/s", stats.forks_per_sec));
+    }
+
+    let visible = area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = app
+        .process_events
+        .iter()
+        .rev()
+        .take(visible)
+        .map(|event| {
+            let (label, color) = match event.kind {
+                EventKind::Started => ("start", Color::Green),
+                EventKind::Exited => ("exit ", Color::Red),
+            };
+            let mut spans = vec![
+                Span::styled(event.time.format("%H:%M:%S ").to_string(), Style::default().fg(Color::DarkGray)),
+                Span::styled(label, Style::default().fg(color).add_modifier(Modifier::BOLD)),
+                Span::raw(format!(" {:>7} {}", event.pid, event.name)),
+            ];
+            if let Some(lifetime) = event.lifetime {
+                spans.push(Span::styled(format!(" (ran {})", format_uptime(lifetime)), Style::default().fg(Color::Gray)));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    let events = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue)),
+    );
+    f.render_widget(events, area);
 }
 
-fn render_network(f: &mut Frame, area: Rect, app: &App) {
+fn render_network(f: &mut Frame, area: Rect, app: &App, hits: &mut HitAreas, profile: Profile) {
+    let interfaces = app.visible_interfaces();
+    let interfaces_height = (interfaces.len() as u16 + 3).clamp(4, 10);
+
+    // Compact screens keep only the tables, leaving the connections most of the room
+    if profile == Profile::Compact {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(interfaces_height.min(6)), Constraint::Min(6)])
+            .split(area);
+        render_interfaces(f, chunks[0], app, &interfaces);
+        render_connections(f, chunks[1], app, hits, profile);
+        return;
+    }
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(6), Constraint::Min(0)])
+        .constraints([
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(interfaces_height),
+            Constraint::Min(8),
+        ])
         .split(area);
+    render_network_totals(f, chunks[0], chunks[1], app);
+    render_interfaces(f, chunks[2], app, &interfaces);
+    render_connections(f, chunks[3], app, hits, profile);
+}
+
+/// Totals and listening sockets in `stats`, and the traffic history in `charts`.
+fn render_network_totals(f: &mut Frame, stats: Rect, charts: Rect, app: &App) {
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(stats);
 
     // Network stats
     let (total_received, total_transmitted) = if let Some(&(rx, tx)) = app.network_history.last() {
@@ -307,7 +1146,7 @@ fn render_network(f: &mut Frame, area: Rect, app: &App) {
         ]),
         Line::from(vec![
             Span::styled("Network Interfaces: ", Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
-            Span::raw(format!("{}", app.networks.len())),
+            Span::raw(format!("{}", app.interface_count())),
         ]),
     ];
 
@@ -319,7 +1158,8 @@ fn render_network(f: &mut Frame, area: Rect, app: &App) {
                 .border_style(Style::default().fg(Color::Blue)),
         )
         .style(Style::default().fg(Color::White));
-    f.render_widget(network_block, chunks[0]);
+    f.render_widget(network_block, top[0]);
+    render_listening(f, top[1], app);
 
     // Network history chart
     let rx_history: Vec<u64> = app.network_history.iter().map(|(rx, _)| *rx / 1024 / 1024).collect(); // Convert to MB
@@ -328,7 +1168,7 @@ fn render_network(f: &mut Frame, area: Rect, app: &App) {
     let chart_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(chunks[1]);
+        .split(charts);
 
     let rx_sparkline = Sparkline::default()
         .block(
@@ -353,16 +1193,190 @@ fn render_network(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(tx_sparkline, chart_chunks[1]);
 }
 
-fn render_disks(f: &mut Frame, area: Rect, app: &App) {
-    let rows = app.disk_usage.iter().map(|(name, used, total)| {
-        let usage_percent = if *total > 0 {
-            (*used as f64 / *total as f64 * 100.0) as u16
+fn render_interfaces(f: &mut Frame, area: Rect, app: &App, interfaces: &[&Interface]) {
+    let counters = |errors: u64, dropped: u64| {
+        let style = if errors > 0 {
+            Style::default().fg(Color::Red)
+        } else if dropped > 0 {
+            Style::default().fg(Color::Yellow)
         } else {
-            0
+            Style::default()
         };
-        
-        let cells = vec![
-            Cell::from(name.clone()),
+        Cell::from(format!("{}/{}", errors, dropped)).style(style)
+    };
+    let rows = interfaces.iter().map(|interface| {
+        let state_color = match interface.state.as_str() {
+            "up" => Color::Green,
+            "down" | "lowerlayerdown" | "notpresent" => Color::Red,
+            _ => Color::Gray,
+        };
+        Row::new(vec![
+            Cell::from(interface.name.clone()),
+            Cell::from(interface.state.clone()).style(Style::default().fg(state_color)),
+            Cell::from(interface.speed.map_or("-".to_string(), |speed| {
+                if speed >= 1000 && speed % 1000 == 0 {
+                    format!("{} Gb/s", speed / 1000)
+                } else {
+                    format!("{} Mb/s", speed)
+                }
+            })),
+            Cell::from(interface.mtu.map_or("-".to_string(), |mtu| mtu.to_string())),
+            Cell::from(interface.mac.clone()),
+            counters(interface.rx_errors, interface.rx_dropped),
+            counters(interface.tx_errors, interface.tx_dropped),
+            Cell::from(interface.addresses.join(", ")),
+        ])
+    });
+
+    let header_cells = ["Interface", "State", "Speed", "MTU", "MAC", "RX err/drop", "TX err/drop", "Addresses"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells).style(Style::default().bg(Color::Blue));
+
+    let hidden = app.interfaces.len() - interfaces.len();
+    let title = if hidden > 0 {
+        format!("Interfaces ({}, {} virtual hidden)", interfaces.len(), hidden)
+    } else {
+        format!("Interfaces ({})", interfaces.len())
+    };
+    let table = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .widths(&[
+            Constraint::Length(14),
+            Constraint::Length(9),
+            Constraint::Length(9),
+            Constraint::Length(6),
+            Constraint::Length(18),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Percentage(100),
+        ]);
+    f.render_widget(table, area);
+}
+
+/// TCP and UDP sockets waiting for peers, with the process behind each.
+fn render_listening(f: &mut Frame, area: Rect, app: &App) {
+    let listening: Vec<_> = app.connections.iter().filter(|c| c.is_listening() && c.protocol != "unix").collect();
+    let visible = area.height.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = listening
+        .iter()
+        .take(if listening.len() > visible { visible.saturating_sub(1) } else { visible })
+        .map(|c| {
+            Line::from(vec![
+                Span::styled(format!("{:<4}", c.protocol), Style::default().fg(Color::Cyan)),
+                Span::raw(format!("{:<28}", c.local)),
+                Span::styled(owner_label(c.pid, c.process.as_deref()), Style::default().fg(Color::Gray)),
+            ])
+        })
+        .collect();
+    if listening.len() > lines.len() {
+        lines.push(Line::styled(format!("... {} more", listening.len() - lines.len()), Style::default().fg(Color::DarkGray)));
+    }
+    let unix = app.connections.iter().filter(|c| c.protocol == "unix" && c.is_listening()).count();
+
+    let summary = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!("Listening ({} tcp/udp, {} unix)", listening.len(), unix))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue)),
+    );
+    f.render_widget(summary, area);
+}
+
+fn owner_label(pid: Option<u32>, process: Option<&str>) -> String {
+    match (pid, process) {
+        (Some(pid), Some(name)) => format!("{} ({})", name, pid),
+        (Some(pid), None) => pid.to_string(),
+        _ => "-".to_string(),
+    }
+}
+
+fn render_connections(f: &mut Frame, area: Rect, app: &App, hits: &mut HitAreas, profile: Profile) {
+    // Addresses are cut short on compact screens so the state and process stay visible
+    let (local_width, remote_width) = if profile == Profile::Compact { (22, 22) } else { (40, 28) };
+    if app.is_remote() {
+        render_placeholder(f, area, "Connections", "Connections are listed for this machine only");
+        return;
+    }
+
+    let filter = &app.connection_filter;
+    let connections = app.visible_connections();
+    let mut title = vec![Span::raw(format!("Connections ({}/{})", connections.len(), app.connections.len()))];
+    if let Some(state) = filter.state {
+        title.push(Span::raw(format!(" | state: {}", state)));
+    }
+    if let Some(editing) = &filter.editing {
+        title.push(Span::styled(format!(" | port: {}_", editing), Style::default().fg(Color::Yellow)));
+    } else if let Some(port) = filter.port {
+        title.push(Span::raw(format!(" | port: {}", port)));
+    }
+
+    let rows = connections.iter().enumerate().map(|(i, c)| {
+        let style = if i == app.selected_connection {
+            Style::default().bg(Color::Yellow).fg(Color::Black)
+        } else if c.is_listening() {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        Row::new(vec![
+            Cell::from(c.protocol),
+            Cell::from(c.local.clone()),
+            Cell::from(c.remote.clone()),
+            Cell::from(c.state),
+            Cell::from(owner_label(c.pid, c.process.as_deref())),
+        ])
+        .style(style)
+    });
+
+    let header_cells = ["Proto", "Local", "Remote", "State", "Process"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells).style(Style::default().bg(Color::Blue));
+
+    let widths = [
+        Constraint::Length(5),
+        Constraint::Length(local_width),
+        Constraint::Length(remote_width),
+        Constraint::Length(11),
+        Constraint::Percentage(100),
+    ];
+    let table = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(Line::from(title))
+                .border_style(Style::default().fg(Color::Green)),
+        )
+        .widths(&widths);
+
+    hits.rows = Some(render_scrolling_table(
+        f,
+        area,
+        table,
+        connections.len(),
+        Some(app.selected_connection),
+        &app.connection_view,
+    ));
+}
+
+fn render_disks(f: &mut Frame, area: Rect, app: &App) {
+    let rows = app.disk_usage.iter().map(|(name, used, total)| {
+        let usage_percent = if *total > 0 {
+            (*used as f64 / *total as f64 * 100.0) as u16
+        } else {
+            0
+        };
+        
+        let cells = vec![
+            Cell::from(name.clone()),
             Cell::from(format_bytes(*used)),
             Cell::from(format_bytes(*total)),
             Cell::from(format_bytes(*total - *used)),
@@ -402,4 +1416,968 @@ fn render_disks(f: &mut Frame, area: Rect, app: &App) {
         ]);
 
     f.render_widget(table, area);
-}
\ No newline at end of file
+}
+
+fn render_services(f: &mut Frame, area: Rect, app: &App, hits: &mut HitAreas) {
+    let header_cells = ["Unit", "Main PID", "Tasks", "CPU%", "Memory"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells).style(Style::default().bg(Color::Blue));
+
+    let rows = app.services.iter().enumerate().map(|(i, service)| {
+        let cells = vec![
+            Cell::from(service.name.clone()),
+            Cell::from(service.main_pid.map_or("-".to_string(), |pid| pid.to_string())),
+            Cell::from(service.tasks.to_string()),
+            Cell::from(format!("{:.1}%", app.process_cpu(service.cpu_usage))),
+            Cell::from(format_bytes(service.memory)),
+        ];
+
+        let style = if i == app.selected_service {
+            Style::default().bg(Color::Yellow).fg(Color::Black)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        Row::new(cells).style(style)
+    });
+
+    let title = if app.services.is_empty() {
+        "Services (no systemd cgroup hierarchy found)".to_string()
+    } else {
+        format!("Services ({}) | CPU%: {}", app.services.len(), app.cpu_mode.describe())
+    };
+
+    let table = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .widths(&[
+            Constraint::Min(30),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(12),
+        ]);
+
+    hits.rows = Some(render_scrolling_table(
+        f,
+        area,
+        table,
+        app.services.len(),
+        Some(app.selected_service),
+        &app.service_view,
+    ));
+}
+
+// Severity levels shared by the gauges (60/80%) and the disk table (75/90%).
+fn severity(percent: f32, warn: f32, critical: f32) -> u8 {
+    if percent > critical {
+        2
+    } else if percent > warn {
+        1
+    } else {
+        0
+    }
+}
+
+fn severity_color(severity: u8) -> Color {
+    match severity {
+        2 => Color::Red,
+        1 => Color::Yellow,
+        _ => Color::Green,
+    }
+}
+
+fn render_hosts(f: &mut Frame, area: Rect, app: &App, hits: &mut HitAreas) {
+    let header_cells = ["Host", "Status", "CPU%", "Memory%", "Load (1/5/15)", "Top Process", "Fullest Disk"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells).style(Style::default().bg(Color::Blue));
+
+    let summaries = app.host_summaries();
+    let rows = summaries.iter().enumerate().map(|(i, summary)| {
+        let status = match app.hosts.get(i) {
+            None => "Local".to_string(),
+            Some(host) => {
+                let host = host.lock().unwrap();
+                match &host.status {
+                    ConnectionStatus::Connecting => "Connecting".to_string(),
+                    ConnectionStatus::Connected if host.is_stale() => "Stale".to_string(),
+                    ConnectionStatus::Connected => "Connected".to_string(),
+                    ConnectionStatus::Disconnected { retry_in, .. } => format!("Retry in {}s", retry_in.as_secs()),
+                }
+            }
+        };
+
+        let worst = [
+            summary.cpu_usage.map(|cpu| severity(cpu, 60.0, 80.0)),
+            summary.memory_percent.map(|mem| severity(mem, 60.0, 80.0)),
+            summary.fullest_disk.as_ref().map(|(_, disk)| severity(*disk, 75.0, 90.0)),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or(0);
+
+        let percent = |value: Option<f32>| value.map_or("-".to_string(), |v| format!("{:.1}%", v));
+        let cells = vec![
+            Cell::from(if summary.source == summary.host_name {
+                summary.source.clone()
+            } else {
+                format!("{} ({})", summary.host_name, summary.source)
+            }),
+            Cell::from(status),
+            Cell::from(percent(summary.cpu_usage)),
+            Cell::from(percent(summary.memory_percent)),
+            Cell::from(summary.load_average.map_or("-".to_string(), |(one, five, fifteen)| {
+                format!("{:.2} {:.2} {:.2}", one, five, fifteen)
+            })),
+            Cell::from(summary.top_process.as_ref().map_or("-".to_string(), |(name, cpu)| {
+                format!("{} ({:.1}%)", name, cpu)
+            })),
+            Cell::from(summary.fullest_disk.as_ref().map_or("-".to_string(), |(name, usage)| {
+                format!("{} ({:.0}%)", name, usage)
+            })),
+        ];
+
+        let style = if app.is_remote() && i == app.selected_host {
+            Style::default().bg(Color::Yellow).fg(Color::Black)
+        } else {
+            Style::default().fg(severity_color(worst))
+        };
+
+        Row::new(cells).style(style)
+    });
+
+    let table = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Hosts (Enter: open host)")
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .widths(&[
+            Constraint::Min(24),
+            Constraint::Length(12),
+            Constraint::Length(8),
+            Constraint::Length(9),
+            Constraint::Length(16),
+            Constraint::Length(24),
+            Constraint::Length(20),
+        ]);
+
+    let selected = app.is_remote().then_some(app.selected_host);
+    hits.rows = Some(render_scrolling_table(f, area, table, summaries.len(), selected, &app.host_view));
+}
+
+/// Splits `width` cells between `segments` in proportion to their values.
+fn stacked_bar(segments: &[(u64, Color)], width: u16) -> Line<'static> {
+    let total: u64 = segments.iter().map(|(value, _)| value).sum();
+    if total == 0 {
+        return Line::from("");
+    }
+
+    let mut spans = Vec::new();
+    let mut cumulative = 0;
+    let mut drawn = 0;
+    for (value, color) in segments {
+        cumulative += value;
+        let end = (cumulative as f64 / total as f64 * width as f64).round() as usize;
+        spans.push(Span::styled("█".repeat(end.saturating_sub(drawn)), Style::default().fg(*color)));
+        drawn = drawn.max(end);
+    }
+    Line::from(spans)
+}
+
+fn render_memory(f: &mut Frame, area: Rect, app: &App) {
+    let system_info = app.get_system_info();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Length(12), Constraint::Min(0)])
+        .split(area);
+
+    let label = Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+    let segments = |m: &crate::meminfo::MemoryBreakdown| {
+        [
+            ("Used", m.used(), Color::Magenta),
+            ("Buffers", m.buffers, Color::Blue),
+            ("Cached", m.cached, Color::Yellow),
+            ("Free", m.free, Color::Green),
+        ]
+    };
+
+    // Stacked bar
+    let bar_text = match &app.memory_breakdown {
+        Some(breakdown) => {
+            let segments = segments(breakdown);
+            let bar_segments: Vec<(u64, Color)> = segments.iter().map(|(_, value, color)| (*value, *color)).collect();
+            let legend = segments
+                .iter()
+                .flat_map(|(name, value, color)| {
+                    [
+                        Span::styled("■ ", Style::default().fg(*color)),
+                        Span::raw(format!("{} {}  ", name, format_bytes(*value))),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            vec![stacked_bar(&bar_segments, chunks[0].width.saturating_sub(2)), Line::from(legend)]
+        }
+        None => vec![Line::from("Detailed memory breakdown requires /proc/meminfo (Linux)")],
+    };
+    let bar = Paragraph::new(bar_text).block(
+        Block::default()
+            .title("Memory Breakdown")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+    f.render_widget(bar, chunks[0]);
+
+    let middle = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+
+    // Detail panel
+    let detail_text: Vec<Line> = match &app.memory_breakdown {
+        Some(m) => {
+            let huge_pages = if m.huge_pages_total > 0 {
+                format!(
+                    "{} of {} free ({} each)",
+                    m.huge_pages_free,
+                    m.huge_pages_total,
+                    format_bytes(m.huge_page_size)
+                )
+            } else {
+                "none reserved".to_string()
+            };
+            [
+                ("Total: ", format_bytes(m.total)),
+                ("Used: ", format_bytes(m.used())),
+                ("Free: ", format_bytes(m.free)),
+                ("Available: ", format_bytes(m.available)),
+                ("Buffers: ", format_bytes(m.buffers)),
+                ("Cached: ", format_bytes(m.cached)),
+                ("Shared: ", format_bytes(m.shared)),
+                ("Dirty/Writeback: ", format!("{} / {}", format_bytes(m.dirty), format_bytes(m.writeback))),
+                ("Huge Pages: ", huge_pages),
+            ]
+            .into_iter()
+            .map(|(name, value)| Line::from(vec![Span::styled(name, label), Span::raw(value)]))
+            .collect()
+        }
+        None => vec![
+            Line::from(vec![Span::styled("Total: ", label), Span::raw(format_bytes(system_info.total_memory))]),
+            Line::from(vec![Span::styled("Used: ", label), Span::raw(format_bytes(system_info.used_memory))]),
+        ],
+    };
+    let details = Paragraph::new(detail_text)
+        .block(
+            Block::default()
+                .title("Memory Details")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue)),
+        )
+        .style(Style::default().fg(Color::White));
+    f.render_widget(details, middle[0]);
+
+    // Swap gauge and details
+    let swap_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(middle[1]);
+
+    let swap_usage = if system_info.total_swap > 0 {
+        ((system_info.used_swap as f64 / system_info.total_swap as f64) * 100.0).min(100.0) as u16
+    } else {
+        0
+    };
+    let swap_gauge = Gauge::default()
+        .block(
+            Block::default()
+                .title(format!(
+                    "Swap ({}/{})",
+                    format_bytes(system_info.used_swap),
+                    format_bytes(system_info.total_swap)
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .gauge_style(Style::default().fg(severity_color(severity(swap_usage as f32, 60.0, 80.0))))
+        .percent(swap_usage)
+        .label(if system_info.total_swap > 0 { format!("{}%", swap_usage) } else { "no swap".to_string() });
+    f.render_widget(swap_gauge, swap_chunks[0]);
+
+    let mut swap_text = vec![
+        Line::from(vec![Span::styled("Total: ", label), Span::raw(format_bytes(system_info.total_swap))]),
+        Line::from(vec![Span::styled("Used: ", label), Span::raw(format_bytes(system_info.used_swap))]),
+        Line::from(vec![
+            Span::styled("Free: ", label),
+            Span::raw(format_bytes(system_info.total_swap.saturating_sub(system_info.used_swap))),
+        ]),
+    ];
+    if let Some(m) = &app.memory_breakdown {
+        swap_text.push(Line::from(vec![Span::styled("Cached: ", label), Span::raw(format_bytes(m.swap_cached))]));
+    }
+    let swap_details = Paragraph::new(swap_text)
+        .block(
+            Block::default()
+                .title("Swap Details")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .style(Style::default().fg(Color::White));
+    f.render_widget(swap_details, swap_chunks[1]);
+
+    render_swap_history(f, chunks[2], app);
+}
+
+const CPU_STATE_COLORS: [Color; 7] = [
+    Color::Green,        // user
+    Color::Blue,         // nice
+    Color::Red,          // system
+    Color::Yellow,       // iowait
+    Color::Magenta,      // irq
+    Color::LightMagenta, // softirq
+    Color::Cyan,         // steal
+];
+
+fn cpu_segments(breakdown: &CpuBreakdown) -> Vec<(u64, Color)> {
+    // Hundredths of a percent keep small states such as steal visible
+    let mut segments: Vec<(u64, Color)> = breakdown
+        .busy()
+        .iter()
+        .zip(CPU_STATE_COLORS)
+        .map(|((_, value), color)| ((value * 100.0) as u64, color))
+        .collect();
+    segments.push(((breakdown.idle * 100.0) as u64, Color::DarkGray));
+    segments
+}
+
+/// Draws one column per sample, bottom-up, with each busy state stacked in its colour.
+fn stacked_history(samples: &[CpuBreakdown], width: u16, height: u16) -> Vec<Line<'static>> {
+    let width = width as usize;
+    let height = height as usize;
+    let visible = &samples[samples.len().saturating_sub(width)..];
+    let padding = width - visible.len();
+
+    (0..height)
+        .map(|row| {
+            // Percentage covered by the middle of this row, counted from the bottom
+            let level = ((height - row) as f32 - 0.5) / height as f32 * 100.0;
+            let mut spans = vec![Span::raw(" ".repeat(padding))];
+            spans.extend(visible.iter().map(|sample| {
+                let mut cumulative = 0.0;
+                let color = sample.busy().iter().zip(CPU_STATE_COLORS).find_map(|((_, value), color)| {
+                    cumulative += value;
+                    (level < cumulative).then_some(color)
+                });
+                match color {
+                    Some(color) => Span::styled("█", Style::default().fg(color)),
+                    None => Span::raw(" "),
+                }
+            }));
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn render_cpu(f: &mut Frame, area: Rect, app: &App) {
+    let Some(stats) = &app.cpu_stats else {
+        let waiting = Paragraph::new("CPU time breakdown requires /proc/stat (Linux) and two samples")
+            .block(
+                Block::default()
+                    .title("CPU Time Breakdown")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Green)),
+            )
+            .style(Style::default().fg(Color::DarkGray));
+        f.render_widget(waiting, area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(4), Constraint::Length(12)])
+        .split(area);
+
+    // Global stacked bar with legend
+    let legend: Vec<Span> = stats
+        .global
+        .busy()
+        .iter()
+        .zip(CPU_STATE_COLORS)
+        .chain(std::iter::once((&("idle", stats.global.idle), Color::DarkGray)))
+        .flat_map(|((name, value), color)| {
+            [
+                Span::styled("■ ", Style::default().fg(color)),
+                Span::raw(format!("{} {:.1}%  ", name, value)),
+            ]
+        })
+        .collect();
+    let global = Paragraph::new(vec![
+        stacked_bar(&cpu_segments(&stats.global), chunks[0].width.saturating_sub(2)),
+        Line::from(legend),
+    ])
+    .block(
+        Block::default()
+            .title(format!(
+                "CPU Time Breakdown ({:.0} context switches/s, {:.0} interrupts/s)",
+                stats.context_switches_per_sec, stats.interrupts_per_sec
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green)),
+    );
+    f.render_widget(global, chunks[0]);
+
+    // Per-core stacked bars
+    render_per_core(f, chunks[1], stats);
+
+    // Stacked history chart
+    let history = Paragraph::new(stacked_history(
+        &app.cpu_breakdown_history,
+        chunks[2].width.saturating_sub(2),
+        chunks[2].height.saturating_sub(2),
+    ))
+    .block(
+        Block::default()
+            .title("CPU Time History")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Green)),
+    );
+    f.render_widget(history, chunks[2]);
+}
+
+fn render_swap_history(f: &mut Frame, area: Rect, app: &App) {
+    let swap_data: Vec<u64> = app.swap_history.iter().map(|&x| x as u64).collect();
+    let swap_sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .title("Swap History")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .data(&swap_data)
+        .max(100)
+        .style(Style::default().fg(Color::Yellow));
+    f.render_widget(swap_sparkline, area);
+}
+
+fn render_per_core(f: &mut Frame, area: Rect, stats: &CpuStats) {
+    let bar_width = area.width.saturating_sub(2 + 8 + 7);
+    let core_lines: Vec<Line> = stats
+        .cores
+        .iter()
+        .enumerate()
+        .map(|(i, core)| {
+            let mut spans = vec![Span::styled(format!("cpu{:<4} ", i), Style::default().fg(Color::Cyan))];
+            spans.extend(stacked_bar(&cpu_segments(core), bar_width).spans);
+            spans.push(Span::raw(format!(" {:>5.1}%", 100.0 - core.idle)));
+            Line::from(spans)
+        })
+        .collect();
+    let cores = Paragraph::new(core_lines).block(
+        Block::default()
+            .title("Per Core")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue)),
+    );
+    f.render_widget(cores, area);
+}
+
+fn render_dashboard(f: &mut Frame, area: Rect, app: &App, dashboard: &Dashboard) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(dashboard.rows.iter().map(|row| row.height).collect::<Vec<_>>())
+        .split(area);
+
+    for (row, row_area) in dashboard.rows.iter().zip(rows.iter()) {
+        let cells = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(row.widgets.iter().map(|(_, width)| *width).collect::<Vec<_>>())
+            .split(*row_area);
+        for ((widget, _), cell) in row.widgets.iter().zip(cells.iter()) {
+            render_widget(f, *cell, app, *widget);
+        }
+    }
+}
+
+fn render_widget(f: &mut Frame, area: Rect, app: &App, widget: Widget) {
+    match widget {
+        Widget::SystemInfo => render_system_info(f, area, &app.get_system_info()),
+        Widget::CpuGauge => render_cpu_gauge(f, area, app),
+        Widget::MemGauge => render_memory_gauge(f, area, &app.get_system_info()),
+        Widget::CpuHistory => render_cpu_history(f, area, app),
+        Widget::MemHistory => render_memory_history(f, area, app),
+        Widget::SwapHistory => render_swap_history(f, area, app),
+        Widget::Pressure => render_pressure(f, area, app),
+        Widget::PerCoreCpu => match &app.cpu_stats {
+            Some(stats) => render_per_core(f, area, stats),
+            None => render_placeholder(f, area, "Per Core", "Waiting for a second /proc/stat sample"),
+        },
+        Widget::TopProcesses { limit } => render_top_processes(f, area, app, limit),
+        Widget::NetRates => render_net_rates(f, area, app),
+        Widget::DiskTable => render_disks(f, area, app),
+        Widget::ProcessEvents => render_process_events(f, area, app),
+    }
+}
+
+fn render_placeholder(f: &mut Frame, area: Rect, title: &str, message: &str) {
+    let placeholder = Paragraph::new(message.to_string())
+        .block(
+            Block::default()
+                .title(title.to_string())
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Blue)),
+        )
+        .style(Style::default().fg(Color::DarkGray));
+    f.render_widget(placeholder, area);
+}
+
+/// The busiest processes by CPU, independent of the Processes tab's sort order.
+fn render_top_processes(f: &mut Frame, area: Rect, app: &App, limit: usize) {
+    let mut processes: Vec<&ProcessInfo> = app.processes.iter().collect();
+    processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap_or(std::cmp::Ordering::Equal));
+
+    let rows = processes.iter().take(limit).map(|process| {
+        let cpu = app.process_cpu(process.cpu_usage);
+        Row::new(vec![
+            Cell::from(process.pid.to_string()),
+            Cell::from(format!("{:.1}", cpu)),
+            Cell::from(format_bytes(process.memory)),
+            Cell::from(process.name.clone()),
+        ])
+        .style(Style::default().fg(severity_color(severity(cpu, 60.0, 80.0))))
+    });
+
+    let header_cells = ["PID", "CPU%", "Memory", "Name"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells).style(Style::default().bg(Color::Blue));
+
+    let table = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Top {} Processes", limit))
+                .border_style(Style::default().fg(Color::Green)),
+        )
+        .widths(&[
+            Constraint::Length(8),
+            Constraint::Length(6),
+            Constraint::Length(10),
+            Constraint::Percentage(100),
+        ]);
+    f.render_widget(table, area);
+}
+
+/// Bytes moved between consecutive refreshes; `network_history` holds running totals.
+fn render_net_rates(f: &mut Frame, area: Rect, app: &App) {
+    let deltas: Vec<(u64, u64)> = app
+        .network_history
+        .windows(2)
+        .map(|pair| (pair[1].0.saturating_sub(pair[0].0), pair[1].1.saturating_sub(pair[0].1)))
+        .collect();
+    let (rx, tx) = deltas.last().copied().unwrap_or((0, 0));
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let rx_data: Vec<u64> = deltas.iter().map(|(rx, _)| *rx).collect();
+    let rx_sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!("Received ({}/refresh)", format_bytes(rx)))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Green)),
+        )
+        .data(&rx_data)
+        .style(Style::default().fg(Color::Green));
+    f.render_widget(rx_sparkline, chunks[0]);
+
+    let tx_data: Vec<u64> = deltas.iter().map(|(_, tx)| *tx).collect();
+    let tx_sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!("Transmitted ({}/refresh)", format_bytes(tx)))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red)),
+        )
+        .data(&tx_data)
+        .style(Style::default().fg(Color::Red));
+    f.render_widget(tx_sparkline, chunks[1]);
+}
+
+fn render_custom(f: &mut Frame, area: Rect, app: &App) {
+    if app.custom_metrics.is_empty() {
+        render_placeholder(f, area, "Custom Metrics", "No plugins configured; add [[plugins]] entries to the config file");
+        return;
+    }
+
+    let charted = |display: Display| -> Vec<(&str, &CustomMetric)> {
+        app.custom_metrics
+            .iter()
+            .flat_map(|plugin| plugin.metrics.iter().map(move |metric| (plugin.name.as_str(), metric)))
+            .filter(|(_, metric)| metric.display == display)
+            .collect()
+    };
+    let gauges = charted(Display::Gauge);
+    let sparklines = charted(Display::Sparkline);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(if gauges.is_empty() { 0 } else { 3 }),
+            Constraint::Length(if sparklines.is_empty() { 0 } else { 7 }),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let gauge_areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, gauges.len().max(1) as u32); gauges.len()])
+        .split(chunks[0]);
+    for ((plugin, metric), gauge_area) in gauges.iter().zip(gauge_areas.iter()) {
+        let max = metric.max.unwrap_or(100.0);
+        let ratio = if max > 0.0 { (metric.value / max).clamp(0.0, 1.0) } else { 0.0 };
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .title(format!("{}/{}", plugin, metric.name))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .gauge_style(Style::default().fg(severity_color(severity(ratio as f32 * 100.0, 60.0, 80.0))))
+            .ratio(ratio)
+            .label(format_metric(metric));
+        f.render_widget(gauge, *gauge_area);
+    }
+
+    let sparkline_areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, sparklines.len().max(1) as u32); sparklines.len()])
+        .split(chunks[1]);
+    for ((plugin, metric), sparkline_area) in sparklines.iter().zip(sparkline_areas.iter()) {
+        // Sparklines take integers and scale to their maximum, so keep two decimals of shape
+        let data: Vec<u64> = metric.history.iter().map(|v| (v.max(0.0) * 100.0).round() as u64).collect();
+        let sparkline = Sparkline::default()
+            .block(
+                Block::default()
+                    .title(format!("{}/{} ({})", plugin, metric.name, format_metric(metric)))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan)),
+            )
+            .data(&data)
+            .style(Style::default().fg(Color::Cyan));
+        f.render_widget(sparkline, *sparkline_area);
+    }
+
+    // Every metric, plus a row for each plugin whose last run failed
+    let mut rows = Vec::new();
+    for plugin in &app.custom_metrics {
+        let updated = plugin
+            .last_run
+            .and_then(|secs| chrono::TimeZone::timestamp_opt(&chrono::Local, secs as i64, 0).single())
+            .map(|time| time.format("%H:%M:%S").to_string())
+            .unwrap_or_else(|| "never".to_string());
+        if let Some(error) = &plugin.error {
+            rows.push(
+                Row::new(vec![plugin.name.clone(), "error".to_string(), updated.clone(), error.clone()])
+                    .style(Style::default().fg(Color::Red)),
+            );
+        }
+        for metric in &plugin.metrics {
+            rows.push(Row::new(vec![plugin.name.clone(), metric.name.clone(), updated.clone(), format_metric(metric)]));
+        }
+    }
+
+    let header_cells = ["Plugin", "Metric", "Updated", "Value"]
+        .iter()
+        .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    let header = Row::new(header_cells).style(Style::default().bg(Color::Blue));
+
+    let table = Table::new(rows)
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Custom Metrics ({} plugins)", app.custom_metrics.len()))
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .widths(&[
+            Constraint::Length(16),
+            Constraint::Length(24),
+            Constraint::Length(10),
+            Constraint::Percentage(100),
+        ]);
+    f.render_widget(table, chunks[2]);
+}
+
+fn format_metric(metric: &CustomMetric) -> String {
+    let value = if metric.value.fract() == 0.0 { format!("{}", metric.value) } else { format!("{:.2}", metric.value) };
+    match &metric.unit {
+        Some(unit) => format!("{} {}", value, unit),
+        None => value,
+    }
+}
+
+fn render_logs(f: &mut Frame, area: Rect, app: &App) {
+    if app.logs.is_empty() {
+        render_placeholder(f, area, "Logs", "No log files configured; add files = [...] to the [logs] section of the config file");
+        return;
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(12), Constraint::Min(0)])
+        .split(area);
+
+    // Stacked over the same 60 refreshes so that spikes line up in time
+    let charts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Length(4), Constraint::Length(4)])
+        .split(chunks[0]);
+    let cpu_data: Vec<u64> = app.cpu_history.iter().map(|&x| x as u64).collect();
+    let memory_data: Vec<u64> = app.memory_history.iter().map(|&x| x as u64).collect();
+    let errors_now = app.logs.error_history.last().copied().unwrap_or(0);
+    // Logs are always tailed on this machine, while CPU and memory come from the selected host
+    let (local, host) = match app.hosts.get(app.selected_host) {
+        Some(host) => (" on this machine".to_string(), format!(" on {}", host.lock().unwrap().address)),
+        None => (String::new(), String::new()),
+    };
+    let series = [
+        (format!("Errors per refresh{} (now {})", local, errors_now), &app.logs.error_history, None, Color::Red),
+        (format!("CPU{} ({:.0}%)", host, app.cpu_history.last().copied().unwrap_or(0.0)), &cpu_data, Some(100), Color::Green),
+        (
+            format!("Memory{} ({:.0}%)", host, app.memory_history.last().copied().unwrap_or(0.0)),
+            &memory_data,
+            Some(100),
+            Color::Magenta,
+        ),
+    ];
+    for ((title, data, max, color), chart_area) in series.into_iter().zip(charts.iter()) {
+        let mut sparkline = Sparkline::default()
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(color)),
+            )
+            .data(data)
+            .style(Style::default().fg(color));
+        if let Some(max) = max {
+            sparkline = sparkline.max(max);
+        }
+        f.render_widget(sparkline, *chart_area);
+    }
+
+    let view = &app.log_view;
+    let mut lines: Vec<Line> = app
+        .logs
+        .files
+        .iter()
+        .filter_map(|file| {
+            let error = file.error.as_ref()?;
+            Some(Line::styled(format!("{}: {}", file.path.display(), error), Style::default().fg(Color::Red)))
+        })
+        .collect();
+    // Unreadable files are listed first and take space from the log lines
+    let height = (chunks[1].height.saturating_sub(2) as usize).saturating_sub(lines.len());
+    view.height.set(height);
+    let visible: Vec<_> = app.logs.visible(view).collect();
+    let end = visible.len().saturating_sub(view.scroll);
+    let start = end.saturating_sub(height);
+    lines.extend(visible[start..end].iter().map(|line| {
+        let color = match line.severity {
+            Severity::Error => Color::Red,
+            Severity::Warning => Color::Yellow,
+            Severity::Info => Color::White,
+        };
+        Line::from(vec![
+            Span::styled(line.time.format("%H:%M:%S ").to_string(), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("{} ", line.source), Style::default().fg(Color::Cyan)),
+            Span::styled(line.text.clone(), Style::default().fg(color)),
+        ])
+    }));
+
+    let mut title = vec![Span::raw(format!("Logs{} ({}/{} lines)", local, visible.len(), app.logs.lines.len()))];
+    match view.min_severity {
+        Severity::Info => {}
+        Severity::Warning => title.push(Span::raw(" | warnings and errors")),
+        Severity::Error => title.push(Span::raw(" | errors only")),
+    }
+    if let Some(editing) = &view.editing {
+        title.push(Span::styled(format!(" | filter: {}_", editing), Style::default().fg(Color::Yellow)));
+    } else if !view.filter.is_empty() {
+        title.push(Span::raw(format!(" | filter: {}", view.filter)));
+    }
+    if view.paused_at.is_some() {
+        let paused = match view.scroll {
+            0 => " | PAUSED (p to resume)".to_string(),
+            scroll => format!(" | PAUSED, {} lines back (G to follow)", scroll),
+        };
+        title.push(Span::styled(paused, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+    }
+
+    let log = Paragraph::new(lines).block(
+        Block::default()
+            .title(Line::from(title))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue)),
+    );
+    f.render_widget(log, chunks[1]);
+}
+
+fn render_open_files(f: &mut Frame, pid: u32, name: &str, files: &[OpenFile], scroll: usize) {
+    let area = centered_rect(110, f.size().height.saturating_sub(4), f.size());
+    let count = |kind: FdKind| files.iter().filter(|file| file.kind == kind).count();
+    let summary = format!(
+        "{} open: {} files, {} sockets, {} pipes, {} devices, {} other",
+        files.len(),
+        count(FdKind::File),
+        count(FdKind::Socket),
+        count(FdKind::Pipe),
+        count(FdKind::Device),
+        count(FdKind::Other)
+    );
+
+    let visible = area.height.saturating_sub(4) as usize;
+    let mut lines = vec![Line::styled(summary, Style::default().fg(Color::Cyan))];
+    lines.extend(files.iter().skip(scroll).take(visible).map(|file| {
+        let color = match file.kind {
+            FdKind::Socket => Color::Green,
+            FdKind::Pipe => Color::Magenta,
+            FdKind::Device => Color::DarkGray,
+            FdKind::File | FdKind::Other => Color::White,
+        };
+        Line::from(vec![
+            Span::styled(format!("{:>5} ", file.fd), Style::default().fg(Color::Yellow)),
+            Span::styled(format!("{:<7}", file.kind.label()), Style::default().fg(color)),
+            Span::raw(file.target.clone()),
+        ])
+    }));
+    lines.push(Line::styled("j/k: scroll  Enter/Esc: close", Style::default().fg(Color::DarkGray)));
+
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!("Open Files: {} (pid {})", name, pid))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(popup, area);
+}
+
+/// A rectangle of the given size centred in `area`, clamped to fit.
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+fn render_dialog(f: &mut Frame, dialog: &Dialog) {
+    let key = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let (title, lines) = match dialog {
+        Dialog::OpenFiles { pid, name, files, scroll } => {
+            render_open_files(f, *pid, name, files, *scroll);
+            return;
+        }
+        Dialog::Renice { pid, name, nice } => (
+            "Change Nice Value",
+            vec![
+                Line::from(format!("{} (pid {})", name, pid)),
+                Line::from(vec![Span::raw("Nice: "), Span::styled(format!("{:+}", nice), key)]),
+                Line::from("k/+: nicer (lower priority)  j/-: less nice"),
+                Line::from("Enter: continue  Esc: cancel"),
+            ],
+        ),
+        Dialog::Ionice { pid, name, priority } => {
+            let classes = [IoClass::RealTime, IoClass::BestEffort, IoClass::Idle]
+                .into_iter()
+                .map(|class| {
+                    let label = match class {
+                        IoClass::RealTime => " realtime ",
+                        IoClass::BestEffort => " best-effort ",
+                        _ => " idle ",
+                    };
+                    if class == priority.class {
+                        Span::styled(label, Style::default().bg(Color::Yellow).fg(Color::Black))
+                    } else {
+                        Span::raw(label)
+                    }
+                })
+                .collect::<Vec<_>>();
+            (
+                "Change IO Priority",
+                vec![
+                    Line::from(format!("{} (pid {})", name, pid)),
+                    Line::from(classes),
+                    Line::from(vec![
+                        Span::raw("Level: "),
+                        Span::styled(
+                            if priority.class == IoClass::Idle { "-".to_string() } else { priority.level.to_string() },
+                            key,
+                        ),
+                        Span::raw(" (0 highest, 7 lowest)"),
+                    ]),
+                    Line::from("h/l: class  j/k: level  Enter: continue  Esc: cancel"),
+                ],
+            )
+        }
+        Dialog::Affinity { pid, name, cores, cursor } => {
+            let boxes = cores
+                .iter()
+                .enumerate()
+                .map(|(cpu, &on)| {
+                    let label = format!("[{}]{} ", if on { "x" } else { " " }, cpu);
+                    if cpu == *cursor {
+                        Span::styled(label, Style::default().bg(Color::Yellow).fg(Color::Black))
+                    } else {
+                        Span::raw(label)
+                    }
+                })
+                .collect::<Vec<_>>();
+            (
+                "Set CPU Affinity",
+                vec![
+                    Line::from(format!("{} (pid {})", name, pid)),
+                    Line::from(boxes),
+                    Line::from("h/l: move  Space: toggle  Enter: continue  Esc: cancel"),
+                ],
+            )
+        }
+        Dialog::Confirm { action, name } => (
+            "Confirm",
+            vec![
+                Line::from(format!("{} ({})?", action.describe(), name)),
+                Line::from(vec![Span::styled("y", key), Span::raw(": yes  "), Span::styled("n", key), Span::raw(": no")]),
+            ],
+        ),
+    };
+
+    let area = centered_rect(70, lines.len() as u16 + 2, f.size());
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .style(Style::default().fg(Color::White))
+        .wrap(Wrap { trim: false });
+    f.render_widget(Clear, area);
+    f.render_widget(popup, area);
+}
@@ -0,0 +1,200 @@
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+
+use crate::app::ProcessInfo;
+
+const MAX_NOTIFICATIONS: usize = 50;
+/// How long an exited process stays listed, waiting for a restart, before it is forgotten.
+const EXITED_RETENTION_MINUTES: i64 = 10;
+
+/// Whether `name` matches `pattern`, where `*` matches any run of characters
+/// and everything else must match exactly.
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = parts.collect();
+    let Some(last) = parts.pop() else {
+        // No `*` at all
+        return rest.is_empty();
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// A process matched by a watch pattern. The entry outlives the process so that
+/// a replacement with the same name is recognised as a restart.
+#[derive(Debug, Clone)]
+pub struct WatchedProcess {
+    pub name: String,
+    pub pid: u32,
+    pub start_time: u64,
+    pub cpu_history: Vec<f32>,
+    pub memory_history: Vec<u64>,
+    pub exited: Option<DateTime<Local>>,
+    pub restarts: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub time: DateTime<Local>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WatchList {
+    pub patterns: Vec<String>,
+    pub processes: Vec<WatchedProcess>,
+    pub notifications: VecDeque<Notification>, // oldest first
+}
+
+impl WatchList {
+    pub fn new(patterns: Vec<String>) -> Self {
+        Self { patterns, ..Self::default() }
+    }
+
+    pub fn is_watched(&self, name: &str) -> bool {
+        self.patterns.iter().any(|pattern| matches(pattern, name))
+    }
+
+    /// Stops watching `name` if any pattern matches it, and otherwise starts
+    /// watching that exact name. Returns whether `name` is now watched.
+    pub fn toggle(&mut self, name: &str) -> bool {
+        if self.is_watched(name) {
+            self.patterns.retain(|pattern| !matches(pattern, name));
+            let patterns = &self.patterns;
+            self.processes
+                .retain(|process| patterns.iter().any(|pattern| matches(pattern, &process.name)));
+            false
+        } else {
+            self.patterns.push(name.to_string());
+            true
+        }
+    }
+
+    /// Records the latest sample of every watched process, noting exits and restarts.
+    pub fn update(&mut self, processes: &[ProcessInfo], now: DateTime<Local>) {
+        let current: Vec<&ProcessInfo> = processes.iter().filter(|p| self.is_watched(&p.name)).collect();
+
+        let mut newly_exited = Vec::new();
+        for (index, entry) in self.processes.iter_mut().enumerate() {
+            let running = current.iter().any(|p| p.pid == entry.pid && p.start_time == entry.start_time);
+            if entry.exited.is_none() && !running {
+                entry.exited = Some(now);
+                newly_exited.push(index);
+            }
+        }
+
+        for process in current {
+            let known = self
+                .processes
+                .iter()
+                .position(|e| e.exited.is_none() && e.pid == process.pid && e.start_time == process.start_time);
+            let index = match known {
+                Some(index) => index,
+                None => match self.processes.iter().position(|e| e.exited.is_some() && e.name == process.name) {
+                    Some(index) => {
+                        let entry = &mut self.processes[index];
+                        let message =
+                            format!("{} restarted: pid {} -> {}", entry.name, entry.pid, process.pid);
+                        entry.pid = process.pid;
+                        entry.start_time = process.start_time;
+                        entry.exited = None;
+                        entry.restarts += 1;
+                        self.notify(now, message);
+                        index
+                    }
+                    None => {
+                        self.processes.push(WatchedProcess {
+                            name: process.name.clone(),
+                            pid: process.pid,
+                            start_time: process.start_time,
+                            cpu_history: Vec::new(),
+                            memory_history: Vec::new(),
+                            exited: None,
+                            restarts: 0,
+                        });
+                        self.processes.len() - 1
+                    }
+                },
+            };
+
+            let entry = &mut self.processes[index];
+            entry.cpu_history.push(process.cpu_usage);
+            entry.memory_history.push(process.memory);
+            if entry.cpu_history.len() > 60 {
+                entry.cpu_history.remove(0);
+                entry.memory_history.remove(0);
+            }
+        }
+
+        // Exits replaced within the same refresh were already reported as restarts
+        for index in newly_exited {
+            let entry = &self.processes[index];
+            if entry.exited.is_some() {
+                let message = format!("{} (pid {}) exited", entry.name, entry.pid);
+                self.notify(now, message);
+            }
+        }
+
+        let retention = chrono::Duration::minutes(EXITED_RETENTION_MINUTES);
+        self.processes.retain(|entry| entry.exited.is_none_or(|exited| now - exited < retention));
+    }
+
+    /// Forgets tracked processes and notifications, e.g. when switching hosts.
+    pub fn reset(&mut self) {
+        self.processes.clear();
+        self.notifications.clear();
+    }
+
+    fn notify(&mut self, time: DateTime<Local>, message: String) {
+        self.notifications.push_back(Notification { time, message });
+        if self.notifications.len() > MAX_NOTIFICATIONS {
+            self.notifications.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process_status::ProcessStatus;
+
+    fn process(name: &str, pid: u32) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            parent: None,
+            name: name.to_string(),
+            cpu_usage: 0.0,
+            memory: 0,
+            status: ProcessStatus::Running,
+            start_time: 0,
+            nice: None,
+            io_priority: None,
+            affinity: None,
+            fds: None,
+            net: None,
+        }
+    }
+
+    #[test]
+    fn exited_entries_are_pruned_after_retention() {
+        let mut watch = WatchList::new(vec!["worker-*".to_string()]);
+        let start = Local::now();
+        watch.update(&[process("worker-1", 10), process("worker-2", 11)], start);
+        watch.update(&[process("worker-1", 10)], start);
+        assert_eq!(watch.processes.len(), 2);
+
+        let later = start + chrono::Duration::minutes(EXITED_RETENTION_MINUTES);
+        watch.update(&[process("worker-1", 10)], later);
+        let names: Vec<_> = watch.processes.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["worker-1"]);
+    }
+}